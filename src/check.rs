@@ -0,0 +1,334 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use itertools::Itertools;
+use serde_json::{json, Value};
+use tower_lsp::lsp_types::{
+    Diagnostic, DiagnosticSeverity, MessageType, NumberOrString, Url, WorkspaceFolder,
+};
+use tower_lsp::LspService;
+
+use crate::diagnostic_helper::DIAGNOSTIC_SOURCE;
+use crate::{Backend, ExtensionConfig};
+
+/// Runs the diagnostics once instead of starting the server, e.g. in CI
+pub static COMMAND: &str = "check";
+
+/// Read from the root when `--config` is not passed
+static DEFAULT_CONFIG_FILE: &str = ".lsp-translations.json";
+
+static USAGE: &str =
+    "Usage: lsp-translations check [--config <file>] [--format text|json|sarif] [<root>]";
+
+static SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Exit code when no errors are found
+pub static EXIT_SUCCESS: i32 = 0;
+/// Exit code when at least one diagnostic is an error
+pub static EXIT_ERRORS: i32 = 1;
+/// Exit code when the arguments or the config are invalid
+pub static EXIT_USAGE: i32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Sarif,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CheckArgs {
+    pub root: PathBuf,
+    pub config: Option<PathBuf>,
+    pub format: OutputFormat,
+}
+
+/// The diagnostics of a file, its path is relative to the root when possible
+#[derive(Debug, PartialEq)]
+pub struct FileDiagnostics {
+    pub path: String,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Parses the arguments after `check`
+pub fn parse_args(args: &[String]) -> Result<CheckArgs, String> {
+    let mut root = None;
+    let mut config = None;
+    let mut format = OutputFormat::Text;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--config" => {
+                let value = args.next().ok_or("Missing value of `--config`")?;
+                config = Some(PathBuf::from(value));
+            }
+            "--format" => {
+                format = match args.next().map(String::as_str) {
+                    Some("text") => OutputFormat::Text,
+                    Some("json") => OutputFormat::Json,
+                    Some("sarif") => OutputFormat::Sarif,
+                    Some(value) => return Err(format!("Unknown format `{}`", value)),
+                    None => return Err("Missing value of `--format`".to_string()),
+                };
+            }
+            _ if arg.starts_with("--") => return Err(format!("Unknown option `{}`", arg)),
+            _ if root.is_none() => root = Some(PathBuf::from(arg)),
+            _ => return Err(format!("Unexpected argument `{}`", arg)),
+        }
+    }
+
+    Ok(CheckArgs {
+        root: root.unwrap_or_else(|| PathBuf::from(".")),
+        config,
+        format,
+    })
+}
+
+/// Loads the translation files and source files of the root and returns their diagnostics
+pub fn check(args: &CheckArgs) -> Result<Vec<FileDiagnostics>, String> {
+    let root = args
+        .root
+        .canonicalize()
+        .map_err(|err| format!("Could not read root {:?}: {}", args.root, err))?;
+
+    let config_path = args
+        .config
+        .clone()
+        .unwrap_or_else(|| root.join(DEFAULT_CONFIG_FILE));
+    let config_text = fs::read_to_string(&config_path)
+        .map_err(|err| format!("Could not read config {:?}: {}", config_path, err))?;
+    let config: ExtensionConfig = serde_json::from_str(&config_text)
+        .map_err(|err| format!("Invalid config {:?}: {}", config_path, err))?;
+
+    let folders = vec![WorkspaceFolder {
+        uri: Url::from_directory_path(&root).map_err(|_| format!("Invalid root {:?}", root))?,
+        name: root
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default(),
+    }];
+
+    // The client is never used as nothing is sent to an editor
+    let mut backend = None;
+    let _service = LspService::new(|client| {
+        let new_backend = Backend::new(client);
+        backend = Some(new_backend.clone());
+        new_backend
+    });
+    let backend = backend.unwrap();
+
+    // Only problems are printed, the output is the list of diagnostics
+    for (message_type, message) in backend.load_workspace(config, &folders) {
+        if message_type == MessageType::ERROR {
+            eprintln!("{}", message);
+        }
+    }
+
+    Ok(backend
+        .get_workspace_diagnostics()
+        .into_iter()
+        .filter(|(_, diagnostics)| !diagnostics.is_empty())
+        .map(|(uri, mut diagnostics)| {
+            let path = uri
+                .to_file_path()
+                .map(|path| get_relative_path(&path, &root))
+                .unwrap_or_else(|_| uri.to_string());
+
+            diagnostics.sort_by_key(|diagnostic| {
+                (
+                    diagnostic.range.start.line,
+                    diagnostic.range.start.character,
+                )
+            });
+
+            FileDiagnostics { path, diagnostics }
+        })
+        .sorted_by(|a, b| a.path.cmp(&b.path))
+        .collect())
+}
+
+/// Runs `check` with the arguments after `check`, prints the diagnostics and returns the exit code
+pub fn run(args: &[String]) -> i32 {
+    let args = match parse_args(args) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            return EXIT_USAGE;
+        }
+    };
+
+    let files = match check(&args) {
+        Ok(files) => files,
+        Err(err) => {
+            eprintln!("{}", err);
+            return EXIT_USAGE;
+        }
+    };
+
+    let output = match args.format {
+        OutputFormat::Text => format_text(&files),
+        OutputFormat::Json => serde_json::to_string_pretty(&format_json(&files)).unwrap(),
+        OutputFormat::Sarif => serde_json::to_string_pretty(&format_sarif(&files)).unwrap(),
+    };
+    println!("{}", output);
+
+    if has_errors(&files) {
+        EXIT_ERRORS
+    } else {
+        EXIT_SUCCESS
+    }
+}
+
+pub fn has_errors(files: &[FileDiagnostics]) -> bool {
+    files
+        .iter()
+        .flat_map(|file| file.diagnostics.iter())
+        .any(|diagnostic| diagnostic.severity == Some(DiagnosticSeverity::ERROR))
+}
+
+/// Formats every diagnostic as `path:line:column: severity[code]: message`
+pub fn format_text(files: &[FileDiagnostics]) -> String {
+    let mut lines = files
+        .iter()
+        .flat_map(|file| {
+            file.diagnostics.iter().map(move |diagnostic| {
+                format!(
+                    "{}:{}:{}: {}{}: {}",
+                    file.path,
+                    diagnostic.range.start.line + 1,
+                    diagnostic.range.start.character + 1,
+                    get_severity_name(diagnostic.severity),
+                    get_code(diagnostic)
+                        .map(|code| format!("[{}]", code))
+                        .unwrap_or_default(),
+                    diagnostic.message
+                )
+            })
+        })
+        .collect::<Vec<String>>();
+
+    let count = |severity| {
+        files
+            .iter()
+            .flat_map(|file| file.diagnostics.iter())
+            .filter(|diagnostic| diagnostic.severity == Some(severity))
+            .count()
+    };
+    lines.push(format!(
+        "{} errors, {} warnings",
+        count(DiagnosticSeverity::ERROR),
+        count(DiagnosticSeverity::WARNING)
+    ));
+
+    lines.join("\n")
+}
+
+pub fn format_json(files: &[FileDiagnostics]) -> Value {
+    json!(files
+        .iter()
+        .flat_map(|file| {
+            file.diagnostics.iter().map(move |diagnostic| {
+                json!({
+                    "file": file.path,
+                    "range": diagnostic.range,
+                    "severity": get_severity_name(diagnostic.severity),
+                    "code": get_code(diagnostic),
+                    "message": diagnostic.message,
+                })
+            })
+        })
+        .collect::<Vec<Value>>())
+}
+
+/// Formats the diagnostics as SARIF 2.1.0, which is supported by code scanning tools
+pub fn format_sarif(files: &[FileDiagnostics]) -> Value {
+    let diagnostics = files
+        .iter()
+        .flat_map(|file| {
+            file.diagnostics
+                .iter()
+                .map(move |diagnostic| (file, diagnostic))
+        })
+        .collect::<Vec<_>>();
+
+    let rules = diagnostics
+        .iter()
+        .filter_map(|(_, diagnostic)| get_code(diagnostic))
+        .unique()
+        .sorted()
+        .map(|code| json!({ "id": code }))
+        .collect::<Vec<Value>>();
+
+    let results = diagnostics
+        .iter()
+        .map(|(file, diagnostic)| {
+            json!({
+                "ruleId": get_code(diagnostic),
+                "level": match diagnostic.severity {
+                    Some(DiagnosticSeverity::ERROR) => "error",
+                    Some(DiagnosticSeverity::WARNING) => "warning",
+                    _ => "note",
+                },
+                "message": { "text": diagnostic.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": file.path },
+                        "region": {
+                            "startLine": diagnostic.range.start.line + 1,
+                            "startColumn": diagnostic.range.start.character + 1,
+                            "endLine": diagnostic.range.end.line + 1,
+                            "endColumn": diagnostic.range.end.character + 1,
+                        },
+                    },
+                }],
+            })
+        })
+        .collect::<Vec<Value>>();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": DIAGNOSTIC_SOURCE,
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+fn get_severity_name(severity: Option<DiagnosticSeverity>) -> &'static str {
+    match severity {
+        Some(DiagnosticSeverity::ERROR) => "error",
+        Some(DiagnosticSeverity::WARNING) => "warning",
+        Some(DiagnosticSeverity::INFORMATION) => "info",
+        _ => "hint",
+    }
+}
+
+fn get_code(diagnostic: &Diagnostic) -> Option<String> {
+    match diagnostic.code.as_ref()? {
+        NumberOrString::String(code) => Some(code.clone()),
+        NumberOrString::Number(code) => Some(code.to_string()),
+    }
+}
+
+/// Returns `path` relative to `root` with `/` separators, or the full path outside of it
+fn get_relative_path(path: &Path, root: &Path) -> String {
+    match path.strip_prefix(root) {
+        Ok(relative) => relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .join("/"),
+        Err(_) => path.to_string_lossy().to_string(),
+    }
+}
+
+#[path = "./tests/check.rs"]
+#[cfg(test)]
+mod test;
@@ -0,0 +1,740 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use tower_lsp::lsp_types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, DiagnosticTag, Location,
+    NumberOrString, Position, Range, Url,
+};
+
+use itertools::Itertools;
+use regex::Regex;
+
+use crate::icu_helper;
+use crate::key_naming_helper::get_key_naming_violations;
+use crate::markup_helper::{get_tag_mismatch, get_tags, get_unbalanced_tags};
+use crate::max_length_helper::{get_length, get_max_length, get_metadata_max_lengths};
+use crate::namespace_helper::NamespacedKey;
+use crate::placeholder_helper::{get_placeholder_mismatch, remove_placeholders, PlaceholderSyntax};
+use crate::tree_sitter_helper::TranslationError;
+use crate::{
+    Definition, DiagnosticsConfig, KeyNamingConfig, MaxLengthRule, UntranslatedConfig,
+    UnusedKeysConfig, Usage,
+};
+
+pub static DIAGNOSTIC_SOURCE: &str = "lsp-translations";
+
+pub static UNUSED_KEY_CODE: &str = "unused-key";
+
+pub static PLACEHOLDER_MISMATCH_CODE: &str = "placeholder-mismatch";
+
+pub static MISSING_PLACEHOLDER_CODE: &str = "missing-placeholder";
+
+pub static ICU_SYNTAX_CODE: &str = "icu-syntax";
+
+pub static ICU_PLURAL_CATEGORY_CODE: &str = "icu-plural-category";
+
+pub static SYNTAX_ERROR_CODE: &str = "syntax-error";
+
+pub static DUPLICATE_KEY_CODE: &str = "duplicate-key";
+
+pub static UNTRANSLATED_CODE: &str = "untranslated";
+
+pub static KEY_NAMING_CODE: &str = "key-naming";
+
+pub static MAX_LENGTH_CODE: &str = "max-length";
+
+pub static UNBALANCED_TAG_CODE: &str = "unbalanced-tag";
+
+pub static TAG_MISMATCH_CODE: &str = "tag-mismatch";
+
+pub static MISSING_KEY_CODE: &str = "missing-key";
+
+pub static MISSING_LANGUAGE_CODE: &str = "missing-language";
+
+/// The codes of every check, only these can be suppressed by name
+static DIAGNOSTIC_CODES: [&str; 14] = [
+    UNUSED_KEY_CODE,
+    PLACEHOLDER_MISMATCH_CODE,
+    MISSING_PLACEHOLDER_CODE,
+    ICU_SYNTAX_CODE,
+    ICU_PLURAL_CATEGORY_CODE,
+    SYNTAX_ERROR_CODE,
+    DUPLICATE_KEY_CODE,
+    UNTRANSLATED_CODE,
+    KEY_NAMING_CODE,
+    MAX_LENGTH_CODE,
+    UNBALANCED_TAG_CODE,
+    TAG_MISMATCH_CODE,
+    MISSING_KEY_CODE,
+    MISSING_LANGUAGE_CODE,
+];
+
+/// Suppresses the diagnostics on the next line, or only the ones with the codes after it,
+/// like `lsp-translations-ignore-next-line unused-key, max-length -- loaded dynamically`
+pub static SUPPRESSION_COMMENT: &str = "lsp-translations-ignore-next-line";
+
+/// Separates the codes of a suppression comment from the reason
+static SUPPRESSION_REASON_DELIMITER: &str = "--";
+
+lazy_static! {
+    /// The comma separated codes directly after the suppression comment
+    static ref SUPPRESSION_CODES_REGEX: Regex =
+        Regex::new(r"^\s*[a-z][a-z0-9-]*(\s*,\s*[a-z][a-z0-9-]*)*").unwrap();
+}
+
+/// Creates a diagnostic on the key of `definition` with the code of the check that found it.
+/// Checks that are disabled unless configured in `diagnostics.rules` have no `severity`.
+pub fn create_diagnostic(
+    definition: &Definition,
+    code: &str,
+    severity: Option<DiagnosticSeverity>,
+    message: String,
+) -> Option<(PathBuf, Diagnostic)> {
+    Some((
+        definition.file.as_ref()?.path.clone(),
+        Diagnostic {
+            range: definition.key_range?,
+            severity,
+            code: Some(NumberOrString::String(code.to_string())),
+            source: Some(DIAGNOSTIC_SOURCE.to_string()),
+            message,
+            ..Default::default()
+        },
+    ))
+}
+
+/// Applies the configured severities to the diagnostics of the file at `path`, and removes
+/// the ones that are turned off or suppressed with a comment in `text`
+pub fn configure_diagnostics(
+    diagnostics: Vec<Diagnostic>,
+    path: Option<&Path>,
+    text: Option<&str>,
+    config: &DiagnosticsConfig,
+) -> Vec<Diagnostic> {
+    let suppressions = text.map(get_suppressions).unwrap_or_default();
+
+    diagnostics
+        .into_iter()
+        .filter_map(|mut diagnostic| {
+            let code = match diagnostic.code.as_ref() {
+                Some(NumberOrString::String(code)) => code.clone(),
+                _ => return Some(diagnostic),
+            };
+
+            diagnostic.severity = config.get_severity(&code, path, diagnostic.severity);
+            diagnostic.severity?;
+
+            let is_suppressed = suppressions
+                .get(&diagnostic.range.start.line)
+                .map_or(false, |codes| codes.is_empty() || codes.contains(&code));
+
+            (!is_suppressed).then(|| diagnostic)
+        })
+        .collect()
+}
+
+/// Returns the suppressed codes per line, where no codes means every code is suppressed
+fn get_suppressions(text: &str) -> HashMap<u32, Vec<String>> {
+    text.lines()
+        .enumerate()
+        .filter_map(|(line, content)| {
+            let index = content.find(SUPPRESSION_COMMENT)?;
+            let arguments = content[index + SUPPRESSION_COMMENT.len()..]
+                .split(SUPPRESSION_REASON_DELIMITER)
+                .next()
+                .unwrap_or_default();
+
+            // Other text, like an explanation or the end of a block comment, is not a code
+            Some((
+                line as u32 + 1,
+                SUPPRESSION_CODES_REGEX
+                    .find(arguments)
+                    .map(|codes| {
+                        codes
+                            .as_str()
+                            .split(',')
+                            .map(str::trim)
+                            .filter(|code| DIAGNOSTIC_CODES.contains(code))
+                            .map(String::from)
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            ))
+        })
+        .collect()
+}
+
+/// Reports keys that are translated in some languages but not in all of them.
+/// Disabled unless it is configured in `diagnostics.rules`.
+pub fn get_missing_language_diagnostics(definitions: &[Definition]) -> Vec<(PathBuf, Diagnostic)> {
+    let languages_per_namespace = definitions
+        .iter()
+        .filter_map(|definition| Some((definition.get_namespace(), definition.get_language()?)))
+        .into_group_map();
+
+    definitions
+        .iter()
+        .into_group_map_by(|definition| (definition.get_namespace(), definition.get_identifier()))
+        .into_iter()
+        .flat_map(|((namespace, identifier), definitions_same_key)| {
+            let translated_languages = definitions_same_key
+                .iter()
+                .filter_map(|definition| definition.get_language())
+                .collect::<HashSet<&String>>();
+
+            let missing_languages = languages_per_namespace
+                .get(&namespace)
+                .map(|languages| {
+                    languages
+                        .iter()
+                        .filter(|language| !translated_languages.contains(*language))
+                        .unique()
+                        .sorted()
+                        .map(|language| format!("`{}`", language))
+                        .join(", ")
+                })
+                .unwrap_or_default();
+
+            if missing_languages.is_empty() {
+                return vec![];
+            }
+
+            definitions_same_key
+                .into_iter()
+                .filter_map(|definition| {
+                    create_diagnostic(
+                        definition,
+                        MISSING_LANGUAGE_CODE,
+                        None,
+                        format!("`{}` is missing in {}", identifier, missing_languages),
+                    )
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Reports a key used in a source file that is not defined in any translation file.
+/// Disabled unless it is configured in `diagnostics.rules`.
+pub fn create_missing_key_diagnostic(range: Range, key: &str) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: None,
+        code: Some(NumberOrString::String(MISSING_KEY_CODE.to_string())),
+        source: Some(DIAGNOSTIC_SOURCE.to_string()),
+        message: format!("`{}` is not defined in any translation file", key),
+        ..Default::default()
+    }
+}
+
+/// Reports the parts of a translation file that could not be parsed
+pub fn get_syntax_error_diagnostics(
+    path: &Path,
+    errors: Vec<TranslationError>,
+) -> Vec<(PathBuf, Diagnostic)> {
+    errors
+        .into_iter()
+        .map(|error| {
+            (
+                path.to_path_buf(),
+                Diagnostic {
+                    range: error.range,
+                    severity: Some(DiagnosticSeverity::ERROR),
+                    code: Some(NumberOrString::String(SYNTAX_ERROR_CODE.to_string())),
+                    source: Some(DIAGNOSTIC_SOURCE.to_string()),
+                    message: error.message,
+                    ..Default::default()
+                },
+            )
+        })
+        .collect()
+}
+
+/// Reports keys that are not used in any of the scanned source files
+pub fn get_unused_key_diagnostics(
+    definitions: &[Definition],
+    usages: &[Usage],
+    config: &UnusedKeysConfig,
+) -> Vec<(PathBuf, Diagnostic)> {
+    let used_keys = usages
+        .iter()
+        .map(|usage| (usage.key.namespace.as_ref(), &usage.key.key))
+        .collect::<HashSet<(Option<&String>, &String)>>();
+
+    let is_used = |definition: &Definition| {
+        let identifier = definition.get_identifier();
+
+        used_keys.contains(&(definition.get_namespace(), identifier))
+            || used_keys.contains(&(None, identifier))
+    };
+
+    // Keys under a YAML anchor are used when one of the keys they are merged into is used
+    let used_merged_keys = definitions
+        .iter()
+        .filter(|definition| is_used(definition))
+        .filter_map(|definition| {
+            Some((
+                definition.file.as_ref().map(|file| &file.path),
+                definition.merged_from.as_ref()?,
+            ))
+        })
+        .collect::<HashSet<(Option<&PathBuf>, &String)>>();
+
+    definitions
+        .iter()
+        .filter(|definition| {
+            !is_used(definition)
+                && !used_merged_keys.contains(&(
+                    definition.file.as_ref().map(|file| &file.path),
+                    &definition.key,
+                ))
+                && !config
+                    .allow
+                    .iter()
+                    .any(|regex| regex.is_match(definition.get_identifier()))
+        })
+        .filter_map(|definition| {
+            let (path, mut diagnostic) = create_diagnostic(
+                definition,
+                UNUSED_KEY_CODE,
+                Some(DiagnosticSeverity::HINT),
+                format!(
+                    "`{}` is not used in the source files",
+                    definition.get_identifier()
+                ),
+            )?;
+
+            diagnostic.tags = Some(vec![DiagnosticTag::UNNECESSARY]);
+            diagnostic.data = Some(serde_json::to_value(definition.get_namespaced_key()).unwrap());
+
+            Some((path, diagnostic))
+        })
+        .collect()
+}
+
+/// Reports keys that are defined more than once for the same language, either in
+/// one file or in several files of that language
+pub fn get_duplicate_key_diagnostics(definitions: &[Definition]) -> Vec<(PathBuf, Diagnostic)> {
+    definitions
+        .iter()
+        .filter(|definition| definition.key_range.is_some())
+        .into_group_map_by(|definition| {
+            (
+                definition.get_namespace(),
+                definition.get_identifier(),
+                &definition.plural_category,
+                definition.get_language(),
+                // Without a language only duplicates within a file can be found
+                definition
+                    .get_language()
+                    .is_none()
+                    .then(|| definition.file.as_ref().map(|file| &file.path)),
+            )
+        })
+        .values()
+        .filter(|duplicates| duplicates.len() > 1)
+        .flat_map(|duplicates| {
+            duplicates.iter().filter_map(move |definition| {
+                let (path, mut diagnostic) = create_diagnostic(
+                    definition,
+                    DUPLICATE_KEY_CODE,
+                    Some(DiagnosticSeverity::WARNING),
+                    match definition.get_language() {
+                        Some(language) => format!(
+                            "`{}` is defined {} times for `{}`",
+                            definition.get_identifier(),
+                            duplicates.len(),
+                            language
+                        ),
+                        None => format!(
+                            "`{}` is defined {} times",
+                            definition.get_identifier(),
+                            duplicates.len()
+                        ),
+                    },
+                )?;
+
+                diagnostic.related_information = Some(
+                    duplicates
+                        .iter()
+                        .filter(|other| !std::ptr::eq(**other, *definition))
+                        .filter_map(|other| {
+                            Some(DiagnosticRelatedInformation {
+                                location: Location::new(
+                                    Url::from_file_path(&other.file.as_ref()?.path).ok()?,
+                                    other.key_range?,
+                                ),
+                                message: format!("Also defined with the value \"{}\"", other.value),
+                            })
+                        })
+                        .collect(),
+                );
+
+                Some((path, diagnostic))
+            })
+        })
+        .collect()
+}
+
+/// Reports translations that are stubs, or that are a copy of the value in `reference_language`
+pub fn get_untranslated_diagnostics(
+    definitions: &[Definition],
+    reference_language: Option<&str>,
+    config: &UntranslatedConfig,
+    syntaxes: &[PlaceholderSyntax],
+) -> Vec<(PathBuf, Diagnostic)> {
+    definitions
+        .iter()
+        .into_group_map_by(|definition| {
+            (
+                definition.get_namespace(),
+                definition.get_identifier(),
+                &definition.plural_category,
+            )
+        })
+        .values()
+        .flat_map(|definitions_same_key| {
+            let reference_value = definitions_same_key
+                .iter()
+                .find(|definition| {
+                    definition.get_language().map(|language| language.as_str())
+                        == reference_language
+                })
+                .map(|definition| &definition.value);
+
+            definitions_same_key.iter().filter_map(move |definition| {
+                let identifier = definition.get_identifier();
+
+                if config.allow.iter().any(|regex| regex.is_match(identifier))
+                    || config
+                        .allow_values
+                        .iter()
+                        .any(|regex| regex.is_match(&definition.value))
+                {
+                    return None;
+                }
+
+                let language = definition.get_language();
+
+                let message = if config
+                    .pattern
+                    .as_ref()
+                    .map_or(false, |pattern| pattern.is_match(&definition.value))
+                {
+                    format!("`{}` is probably untranslated", identifier)
+                } else if reference_language.is_some()
+                    && language.map(|language| language.as_str()) != reference_language
+                    && Some(&definition.value) == reference_value
+                    // Values without text, like `{{count}}` or `-`, are the same in every language
+                    && remove_placeholders(&definition.value, syntaxes)
+                        .chars()
+                        .any(char::is_alphabetic)
+                {
+                    format!(
+                        "`{}` is probably untranslated, the value is the same as in `{}`",
+                        identifier,
+                        reference_language.unwrap()
+                    )
+                } else {
+                    return None;
+                };
+
+                let (path, mut diagnostic) = create_diagnostic(
+                    definition,
+                    UNTRANSLATED_CODE,
+                    Some(DiagnosticSeverity::INFORMATION),
+                    message,
+                )?;
+
+                diagnostic.range = definition.range.unwrap_or(diagnostic.range);
+
+                Some((path, diagnostic))
+            })
+        })
+        .collect()
+}
+
+/// Reports translations that are longer than the maximum length of their key
+pub fn get_max_length_diagnostics(
+    definitions: &[Definition],
+    arb_metadata: &[Definition],
+    rules: &[MaxLengthRule],
+) -> Vec<(PathBuf, Diagnostic)> {
+    let metadata_max_lengths = get_metadata_max_lengths(arb_metadata);
+
+    definitions
+        .iter()
+        .filter_map(|definition| {
+            let max_length = get_max_length(definition, &metadata_max_lengths, rules)?;
+            let length = get_length(&definition.value);
+
+            if length <= max_length {
+                return None;
+            }
+
+            let (path, mut diagnostic) = create_diagnostic(
+                definition,
+                MAX_LENGTH_CODE,
+                Some(DiagnosticSeverity::WARNING),
+                format!(
+                    "`{}` is {} characters long, at most {} are allowed",
+                    definition.get_identifier(),
+                    length,
+                    max_length
+                ),
+            )?;
+
+            diagnostic.range = definition.range.unwrap_or(diagnostic.range);
+
+            Some((path, diagnostic))
+        })
+        .collect()
+}
+
+/// Reports keys in the translation files that do not follow the naming rules
+pub fn get_key_naming_diagnostics(
+    definitions: &[Definition],
+    config: &KeyNamingConfig,
+) -> Vec<(PathBuf, Diagnostic)> {
+    definitions
+        .iter()
+        .filter_map(|definition| {
+            let violations = get_key_naming_violations(
+                definition.get_identifier(),
+                definition.file.as_ref().map(|file| file.path.as_path()),
+                config,
+            );
+
+            if violations.is_empty() {
+                return None;
+            }
+
+            let (path, mut diagnostic) = create_diagnostic(
+                definition,
+                KEY_NAMING_CODE,
+                Some(DiagnosticSeverity::WARNING),
+                create_key_naming_message(definition.get_identifier(), &violations),
+            )?;
+
+            diagnostic.data = Some(serde_json::to_value(definition.get_namespaced_key()).unwrap());
+
+            Some((path, diagnostic))
+        })
+        .collect()
+}
+
+/// Reports a key used in a source file that does not follow the naming rules
+pub fn create_key_naming_diagnostic(
+    range: Range,
+    key: &NamespacedKey,
+    config: &KeyNamingConfig,
+) -> Option<Diagnostic> {
+    let violations = get_key_naming_violations(&key.key, None, config);
+
+    if violations.is_empty() {
+        return None;
+    }
+
+    Some(Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(KEY_NAMING_CODE.to_string())),
+        source: Some(DIAGNOSTIC_SOURCE.to_string()),
+        message: create_key_naming_message(&key.key, &violations),
+        data: Some(serde_json::to_value(key).unwrap()),
+        ..Default::default()
+    })
+}
+
+fn create_key_naming_message(key: &str, violations: &[String]) -> String {
+    format!("`{}` {}", key, violations.join(", "))
+}
+
+/// Reports translations with other placeholders than the translation in `reference_language`
+pub fn get_placeholder_diagnostics(
+    definitions: &[Definition],
+    reference_language: &str,
+    syntaxes: &[PlaceholderSyntax],
+) -> Vec<(PathBuf, Diagnostic)> {
+    definitions
+        .iter()
+        .into_group_map_by(|definition| (definition.get_namespace(), definition.get_identifier()))
+        .values()
+        .flat_map(|definitions_same_key| {
+            definitions_same_key.iter().filter_map(move |definition| {
+                let mismatch = get_placeholder_mismatch(
+                    definition,
+                    definitions_same_key,
+                    reference_language,
+                    syntaxes,
+                )?;
+
+                let (path, mut diagnostic) = create_diagnostic(
+                    definition,
+                    PLACEHOLDER_MISMATCH_CODE,
+                    Some(DiagnosticSeverity::WARNING),
+                    format!(
+                        "Placeholders differ from `{}`: {}",
+                        reference_language, mismatch
+                    ),
+                )?;
+
+                // The placeholders are in the value, not the key
+                diagnostic.range = definition.range.unwrap_or(diagnostic.range);
+
+                Some((path, diagnostic))
+            })
+        })
+        .collect()
+}
+
+/// Reports translations with unbalanced tags, and with other tags than the
+/// translation in `reference_language` when it is known
+pub fn get_markup_diagnostics(
+    definitions: &[Definition],
+    reference_language: Option<&str>,
+) -> Vec<(PathBuf, Diagnostic)> {
+    definitions
+        .iter()
+        .into_group_map_by(|definition| (definition.get_namespace(), definition.get_identifier()))
+        .values()
+        .flat_map(|definitions_same_key| {
+            definitions_same_key.iter().flat_map(move |definition| {
+                let mut problems = get_unbalanced_tags(&get_tags(&definition.value))
+                    .into_iter()
+                    .map(|problem| (UNBALANCED_TAG_CODE, format!("Unbalanced tags: {}", problem)))
+                    .collect::<Vec<(&str, String)>>();
+
+                if let Some(mismatch) = reference_language.and_then(|reference_language| {
+                    get_tag_mismatch(definition, definitions_same_key, reference_language)
+                }) {
+                    problems.push((
+                        TAG_MISMATCH_CODE,
+                        format!(
+                            "Tags differ from `{}`: {}",
+                            reference_language.unwrap(),
+                            mismatch
+                        ),
+                    ));
+                }
+
+                problems.into_iter().filter_map(move |(code, message)| {
+                    let (path, mut diagnostic) = create_diagnostic(
+                        definition,
+                        code,
+                        Some(DiagnosticSeverity::WARNING),
+                        message,
+                    )?;
+
+                    // The tags are in the value, not the key
+                    diagnostic.range = definition.range.unwrap_or(diagnostic.range);
+
+                    Some((path, diagnostic))
+                })
+            })
+        })
+        .collect()
+}
+
+/// Reports ICU messages with syntax errors or plural categories the language does not use
+pub fn get_icu_diagnostics(definitions: &[Definition]) -> Vec<(PathBuf, Diagnostic)> {
+    definitions
+        .iter()
+        .flat_map(|definition| {
+            let problems = match icu_helper::parse_message(&definition.value) {
+                Ok(parts) => icu_helper::get_plural_category_errors(
+                    &parts,
+                    definition.get_language().map(|language| language.as_str()),
+                )
+                .into_iter()
+                .map(|message| {
+                    (
+                        ICU_PLURAL_CATEGORY_CODE,
+                        DiagnosticSeverity::WARNING,
+                        message,
+                        None,
+                    )
+                })
+                .collect(),
+                Err(error) => vec![(
+                    ICU_SYNTAX_CODE,
+                    DiagnosticSeverity::ERROR,
+                    error.message,
+                    Some(error.offset),
+                )],
+            };
+
+            problems
+                .into_iter()
+                .filter_map(move |(code, severity, message, offset)| {
+                    let (path, mut diagnostic) =
+                        create_diagnostic(definition, code, Some(severity), message)?;
+
+                    diagnostic.range = offset
+                        .and_then(|offset| get_value_offset_range(definition, offset))
+                        .or(definition.range)
+                        .unwrap_or(diagnostic.range);
+
+                    Some((path, diagnostic))
+                })
+        })
+        .collect()
+}
+
+/// Returns the range of the character at the byte `offset` of the value, or `None` when the
+/// value is not written as-is on a single line, e.g. when it contains escape sequences
+fn get_value_offset_range(definition: &Definition, offset: usize) -> Option<Range> {
+    let range = definition.range?;
+    if range.start.line != range.end.line || !definition.value.is_char_boundary(offset) {
+        return None;
+    }
+
+    let value_length = definition.value.encode_utf16().count() as u32;
+    let quote_length =
+        match (range.end.character - range.start.character).checked_sub(value_length)? {
+            0 => 0,
+            2 => 1,
+            _ => return None,
+        };
+
+    let start = range.start.character
+        + quote_length
+        + definition.value[..offset].encode_utf16().count() as u32;
+    let length = definition.value[offset..]
+        .chars()
+        .next()
+        .map_or(0, |char| char.len_utf16() as u32);
+
+    Some(Range::new(
+        Position::new(range.start.line, start),
+        Position::new(range.start.line, start + length),
+    ))
+}
+
+/// Reports a translation call that does not pass every placeholder of its translations
+pub fn create_missing_placeholder_diagnostic(
+    range: Range,
+    key: &str,
+    missing_names: &[String],
+) -> Diagnostic {
+    let names = missing_names
+        .iter()
+        .map(|name| format!("`{}`", name))
+        .join(", ");
+
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(MISSING_PLACEHOLDER_CODE.to_string())),
+        source: Some(DIAGNOSTIC_SOURCE.to_string()),
+        message: match missing_names.len() {
+            1 => format!("`{}` expects the placeholder {}", key, names),
+            _ => format!("`{}` expects the placeholders {}", key, names),
+        },
+        ..Default::default()
+    }
+}
+
+#[path = "./tests/diagnostic_helper.rs"]
+#[cfg(test)]
+mod test;
@@ -1,5 +1,5 @@
-use lsp_document::IndexedText;
-use tower_lsp::lsp_types::Url;
+use lsp_document::{IndexedText, Pos, TextChange, TextMap};
+use tower_lsp::lsp_types::{Position, TextDocumentContentChangeEvent, Url};
 
 #[derive(Clone)]
 pub struct FullTextDocument {
@@ -27,3 +27,59 @@ impl FullTextDocument {
         }
     }
 }
+
+/// Converts the positions of `lsp_document` from and to the positions of tower-lsp.
+/// `lsp_document` has its own version of `lsp_types`, so its `TextAdapter` can't be used.
+pub trait LspTextAdapter {
+    fn pos_to_lsp_pos(&self, pos: &Pos) -> Option<Position>;
+    fn lsp_pos_to_pos(&self, lsp_pos: &Position) -> Option<Pos>;
+    fn lsp_change_to_change(
+        &self,
+        lsp_change: TextDocumentContentChangeEvent,
+    ) -> Option<TextChange>;
+}
+
+impl<T: TextMap> LspTextAdapter for T {
+    fn pos_to_lsp_pos(&self, pos: &Pos) -> Option<Position> {
+        let line = self.substr(self.line_range(pos.line)?)?;
+        let character = line.get(..pos.col as usize)?.encode_utf16().count();
+
+        Some(Position::new(pos.line, character as u32))
+    }
+
+    fn lsp_pos_to_pos(&self, lsp_pos: &Position) -> Option<Pos> {
+        let line = self.substr(self.line_range(lsp_pos.line)?)?;
+
+        let mut character = 0;
+        for (col, char) in line.char_indices() {
+            if character == lsp_pos.character as usize {
+                return Some(Pos::new(lsp_pos.line, col as u32));
+            }
+            character += char.len_utf16();
+        }
+
+        // The position after the last character of the line
+        if character == lsp_pos.character as usize {
+            Some(Pos::new(lsp_pos.line, line.len() as u32))
+        } else {
+            None
+        }
+    }
+
+    fn lsp_change_to_change(
+        &self,
+        lsp_change: TextDocumentContentChangeEvent,
+    ) -> Option<TextChange> {
+        let range = match lsp_change.range {
+            Some(range) => {
+                Some(self.lsp_pos_to_pos(&range.start)?..self.lsp_pos_to_pos(&range.end)?)
+            }
+            None => None,
+        };
+
+        Some(TextChange {
+            range,
+            patch: lsp_change.text,
+        })
+    }
+}
@@ -0,0 +1,362 @@
+use itertools::Itertools;
+
+use crate::plural_helper::{get_plural_categories, PLURAL_CATEGORIES};
+
+/// A part of an ICU MessageFormat message
+#[derive(Debug, PartialEq)]
+pub enum IcuPart {
+    Text(String),
+    /// `#` within a plural option, which is replaced by the number
+    Pound,
+    /// `{name}` or `{name, number}`
+    Argument {
+        name: String,
+        format: Option<String>,
+    },
+    /// `{name, plural, one {...} other {...}}`, `selectordinal` or `select`
+    Select {
+        name: String,
+        kind: SelectKind,
+        options: Vec<IcuOption>,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SelectKind {
+    Plural,
+    SelectOrdinal,
+    Select,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct IcuOption {
+    pub selector: String,
+    pub message: Vec<IcuPart>,
+}
+
+/// A syntax error with the byte offset in the message it was found at
+#[derive(Debug, PartialEq)]
+pub struct IcuError {
+    pub message: String,
+    pub offset: usize,
+}
+
+struct IcuParser<'a> {
+    text: &'a str,
+    offset: usize,
+}
+
+impl<'a> IcuParser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.text[self.offset..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let char = self.peek()?;
+        self.offset += char.len_utf8();
+        Some(char)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().map_or(false, char::is_whitespace) {
+            self.next();
+        }
+    }
+
+    fn read_word(&mut self) -> &'a str {
+        let start = self.offset;
+        while self
+            .peek()
+            .map_or(false, |char| !char.is_whitespace() && !",{}".contains(char))
+        {
+            self.next();
+        }
+        &self.text[start..self.offset]
+    }
+
+    fn error<T>(&self, message: String, offset: usize) -> Result<T, IcuError> {
+        Err(IcuError { message, offset })
+    }
+
+    /// Parses text and arguments until the end of the text or the `}` that closes an option
+    fn parse_message(
+        &mut self,
+        in_plural: bool,
+        is_nested: bool,
+    ) -> Result<Vec<IcuPart>, IcuError> {
+        let mut parts = vec![];
+        let mut text = String::new();
+
+        while let Some(char) = self.peek() {
+            match char {
+                '{' => {
+                    if !text.is_empty() {
+                        parts.push(IcuPart::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(self.parse_argument(in_plural)?);
+                }
+                '}' if is_nested => break,
+                '}' => return self.error("Unexpected `}`".to_string(), self.offset),
+                '#' if in_plural => {
+                    self.next();
+                    if !text.is_empty() {
+                        parts.push(IcuPart::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(IcuPart::Pound);
+                }
+                '\'' => {
+                    self.next();
+                    match self.peek() {
+                        // `''` is an escaped apostrophe
+                        Some('\'') => {
+                            self.next();
+                            text.push('\'');
+                        }
+                        // `'{'` quotes syntax characters until the next apostrophe
+                        Some(quoted) if "{}#|".contains(quoted) => {
+                            while let Some(quoted) = self.next() {
+                                if quoted == '\'' {
+                                    break;
+                                }
+                                text.push(quoted);
+                            }
+                        }
+                        _ => text.push('\''),
+                    }
+                }
+                _ => {
+                    self.next();
+                    text.push(char);
+                }
+            }
+        }
+
+        if !text.is_empty() {
+            parts.push(IcuPart::Text(text));
+        }
+
+        Ok(parts)
+    }
+
+    fn parse_argument(&mut self, in_plural: bool) -> Result<IcuPart, IcuError> {
+        let start = self.offset;
+        let unclosed = |parser: &Self| parser.error("Unclosed `{`".to_string(), start);
+
+        self.next();
+        self.skip_whitespace();
+
+        let name = self.read_word().to_string();
+        if name.is_empty() {
+            return match self.peek() {
+                None => unclosed(self),
+                Some(_) => self.error("Expected an argument name".to_string(), self.offset),
+            };
+        }
+
+        self.skip_whitespace();
+        match self.next() {
+            Some('}') => {
+                return Ok(IcuPart::Argument { name, format: None });
+            }
+            Some(',') => {}
+            Some(_) => {
+                return self.error(
+                    format!("Expected `,` or `}}` after `{}`", name),
+                    self.offset - 1,
+                )
+            }
+            None => return unclosed(self),
+        }
+
+        self.skip_whitespace();
+        let format = self.read_word().to_string();
+        self.skip_whitespace();
+
+        let kind = match format.as_str() {
+            "plural" => SelectKind::Plural,
+            "selectordinal" => SelectKind::SelectOrdinal,
+            "select" => SelectKind::Select,
+            _ => {
+                // Styles like `{amount, number, ::currency/EUR}` are kept as-is
+                match self.next() {
+                    Some('}') => {}
+                    Some(',') => {
+                        let mut depth = 0;
+                        loop {
+                            match self.next() {
+                                Some('{') => depth += 1,
+                                Some('}') if depth == 0 => break,
+                                Some('}') => depth -= 1,
+                                Some(_) => {}
+                                None => return unclosed(self),
+                            }
+                        }
+                    }
+                    Some(_) => {
+                        return self.error(
+                            format!("Expected `,` or `}}` after `{}`", format),
+                            self.offset - 1,
+                        )
+                    }
+                    None => return unclosed(self),
+                }
+
+                return Ok(IcuPart::Argument {
+                    name,
+                    format: Some(format).filter(|format| !format.is_empty()),
+                });
+            }
+        };
+
+        match self.next() {
+            Some(',') => {}
+            None => return unclosed(self),
+            Some(_) => {
+                return self.error(format!("Expected `,` after `{}`", format), self.offset - 1)
+            }
+        }
+
+        let mut options = vec![];
+        loop {
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some('}') => {
+                    self.next();
+                    break;
+                }
+                None => return unclosed(self),
+                _ => {}
+            }
+
+            let selector_offset = self.offset;
+            let selector = self.read_word().to_string();
+
+            if kind != SelectKind::Select && selector.starts_with("offset:") {
+                // The number can also follow after whitespace, like `offset: 1`
+                let mut number = selector["offset:".len()..].to_string();
+                if number.is_empty() {
+                    self.skip_whitespace();
+                    number = self.read_word().to_string();
+                }
+
+                if number.is_empty() || !number.chars().all(|char| char.is_ascii_digit()) {
+                    return self.error(
+                        "Expected a number after `offset:`".to_string(),
+                        selector_offset,
+                    );
+                }
+                continue;
+            }
+
+            if selector.is_empty() {
+                return self.error("Expected a selector".to_string(), self.offset);
+            }
+
+            self.skip_whitespace();
+            if self.peek() != Some('{') {
+                return match self.peek() {
+                    None => unclosed(self),
+                    Some(_) => {
+                        self.error(format!("Expected `{{` after `{}`", selector), self.offset)
+                    }
+                };
+            }
+            self.next();
+
+            let message = self.parse_message(in_plural || kind != SelectKind::Select, true)?;
+            if self.next().is_none() {
+                return self.error("Unclosed `{`".to_string(), selector_offset);
+            }
+
+            options.push(IcuOption { selector, message });
+        }
+
+        if !options.iter().any(|option| option.selector == "other") {
+            return self.error(
+                format!("`{}` is missing the required `other` option", name),
+                start,
+            );
+        }
+
+        Ok(IcuPart::Select {
+            name,
+            kind,
+            options,
+        })
+    }
+}
+
+/// Parses an ICU MessageFormat message
+pub fn parse_message(text: &str) -> Result<Vec<IcuPart>, IcuError> {
+    IcuParser { text, offset: 0 }.parse_message(false, false)
+}
+
+/// Returns the plural selectors that do not exist, or that `language` does not use
+pub fn get_plural_category_errors(parts: &[IcuPart], language: Option<&str>) -> Vec<String> {
+    let mut errors = vec![];
+
+    for part in parts {
+        if let IcuPart::Select { kind, options, .. } = part {
+            for option in options {
+                if *kind != SelectKind::Select && !option.selector.starts_with('=') {
+                    let language_categories = language
+                        .filter(|_| *kind == SelectKind::Plural)
+                        .and_then(get_plural_categories);
+
+                    if !PLURAL_CATEGORIES.contains(&option.selector.as_str()) {
+                        errors.push(format!("Unknown plural category `{}`", option.selector));
+                    } else if let Some(language_categories) = language_categories {
+                        if !language_categories.contains(&option.selector.as_str()) {
+                            errors.push(format!(
+                                "`{}` is not a plural category of `{}`",
+                                option.selector,
+                                language.unwrap()
+                            ));
+                        }
+                    }
+                }
+
+                errors.append(&mut get_plural_category_errors(&option.message, language));
+            }
+        }
+    }
+
+    errors
+}
+
+/// Whether the message contains plural or select arguments that are worth expanding
+pub fn has_select(parts: &[IcuPart]) -> bool {
+    parts
+        .iter()
+        .any(|part| matches!(part, IcuPart::Select { .. }))
+}
+
+/// Renders a message on a single line with its options listed,
+/// like `[count: **one** # item · **other** # items]`, for the hover table
+pub fn render_message(parts: &[IcuPart]) -> String {
+    parts
+        .iter()
+        .map(|part| match part {
+            IcuPart::Text(text) => text.escape_debug().to_string().replace("|", "\\|"),
+            IcuPart::Pound => "#".to_string(),
+            IcuPart::Argument { name, .. } => format!("{{{}}}", name),
+            IcuPart::Select { name, options, .. } => format!(
+                "[{}: {}]",
+                name,
+                options
+                    .iter()
+                    .map(|option| format!(
+                        "**{}** {}",
+                        option.selector,
+                        render_message(&option.message)
+                    ))
+                    .join(" · ")
+            ),
+        })
+        .collect()
+}
+
+#[path = "./tests/icu_helper.rs"]
+#[cfg(test)]
+mod test;
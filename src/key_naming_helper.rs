@@ -0,0 +1,141 @@
+use std::path::Path;
+
+use itertools::Itertools;
+
+use crate::string_helper::TRANSLATION_KEY_DIVIDER;
+use crate::KeyNamingConfig;
+
+/// Returns the rules of `config` that `key` violates. The required prefix
+/// can only be checked when the translation file of the key is known.
+pub fn get_key_naming_violations(
+    key: &str,
+    path: Option<&Path>,
+    config: &KeyNamingConfig,
+) -> Vec<String> {
+    let mut violations = vec![];
+    let segments = key.split(TRANSLATION_KEY_DIVIDER).collect::<Vec<&str>>();
+
+    if let Some(segment_regex) = config.segment.as_ref() {
+        violations.extend(
+            segments
+                .iter()
+                .filter(|segment| !segment_regex.is_match(segment))
+                .unique()
+                .map(|segment| format!("segment `{}` does not match `{}`", segment, segment_regex)),
+        );
+    }
+
+    if let Some(max_depth) = config.max_depth {
+        if segments.len() > max_depth {
+            violations.push(format!(
+                "has {} segments where at most {} are allowed",
+                segments.len(),
+                max_depth
+            ));
+        }
+    }
+
+    violations.extend(
+        key.chars()
+            .filter(|char| config.forbidden_characters.contains(*char))
+            .unique()
+            .map(|char| format!("contains the forbidden character `{}`", char)),
+    );
+
+    if let Some(path) = path {
+        violations.extend(
+            config
+                .required_prefixes
+                .iter()
+                .filter(|rule| rule.file.is_match(&path.to_string_lossy()))
+                .filter(|rule| !key.starts_with(rule.prefix.as_str()))
+                .map(|rule| format!("does not start with `{}`", rule.prefix)),
+        );
+    }
+
+    violations
+}
+
+/// Renames the key as written in a file to `snake_case` without forbidden characters.
+///
+/// Quotes around the key and a namespace before `namespace_separator` are kept.
+/// Returns `None` when the rename does not change the key or does not fix it.
+pub fn get_renamed_key(
+    text: &str,
+    namespace_separator: Option<&str>,
+    config: &KeyNamingConfig,
+) -> Option<String> {
+    let quote = text
+        .chars()
+        .next()
+        .filter(|char| (*char == '"' || *char == '\'') && text.len() > 1 && text.ends_with(*char));
+    let unquoted = match quote {
+        Some(_) => &text[1..text.len() - 1],
+        None => text,
+    };
+
+    let (namespace, key) = match namespace_separator.and_then(|separator| {
+        unquoted
+            .split_once(separator)
+            .map(|(namespace, key)| (format!("{}{}", namespace, separator), key))
+    }) {
+        Some((namespace, key)) => (namespace, key),
+        None => (String::new(), unquoted),
+    };
+
+    let renamed_key = key
+        .split(TRANSLATION_KEY_DIVIDER)
+        .map(|segment| to_snake_case(segment, &config.forbidden_characters))
+        .join(TRANSLATION_KEY_DIVIDER);
+
+    let is_fixed = renamed_key.split(TRANSLATION_KEY_DIVIDER).all(|segment| {
+        !segment.is_empty()
+            && config
+                .segment
+                .as_ref()
+                .map_or(true, |segment_regex| segment_regex.is_match(segment))
+    }) && !renamed_key
+        .chars()
+        .any(|char| config.forbidden_characters.contains(char));
+
+    if renamed_key == key || !is_fixed {
+        return None;
+    }
+
+    let quote = quote.map(String::from).unwrap_or_default();
+
+    Some(format!("{}{}{}{}", quote, namespace, renamed_key, quote))
+}
+
+/// Converts `camelCase`, `kebab-case` and words with spaces to `snake_case`
+fn to_snake_case(segment: &str, forbidden_characters: &str) -> String {
+    let mut snake_case = String::new();
+    let mut previous: Option<char> = None;
+
+    for char in segment.chars() {
+        if char.is_whitespace() || char == '-' || forbidden_characters.contains(char) {
+            snake_case.push('_');
+        } else {
+            if char.is_uppercase()
+                && previous.map_or(false, |previous| {
+                    previous.is_lowercase() || previous.is_numeric()
+                })
+            {
+                snake_case.push('_');
+            }
+
+            snake_case.extend(char.to_lowercase());
+        }
+
+        previous = Some(char);
+    }
+
+    snake_case
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .join("_")
+}
+
+#[path = "./tests/key_naming_helper.rs"]
+#[cfg(test)]
+mod test;
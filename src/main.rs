@@ -10,6 +10,22 @@ mod tests_completion;
 #[cfg(test)]
 mod tests_completion_yml;
 
+#[path = "./tests/completion_yml_anchors.rs"]
+#[cfg(test)]
+mod tests_completion_yml_anchors;
+
+#[path = "./tests/completion_hierarchical.rs"]
+#[cfg(test)]
+mod tests_completion_hierarchical;
+
+#[path = "./tests/completion_preview.rs"]
+#[cfg(test)]
+mod tests_completion_preview;
+
+#[path = "./tests/completion_value_search.rs"]
+#[cfg(test)]
+mod tests_completion_value_search;
+
 #[path = "./tests/completion_php.rs"]
 #[cfg(test)]
 mod tests_completion_php;
@@ -26,6 +42,10 @@ mod tests_completion_exclude;
 #[cfg(test)]
 mod tests_completion_invalid_translation_file;
 
+#[path = "./tests/completion_non_string_values.rs"]
+#[cfg(test)]
+mod tests_completion_non_string_values;
+
 #[path = "./tests/hover.rs"]
 #[cfg(test)]
 mod tests_hover;
@@ -34,23 +54,125 @@ mod tests_hover;
 #[cfg(test)]
 mod tests_hover_per_language_file;
 
+#[path = "./tests/hover_per_language_directory.rs"]
+#[cfg(test)]
+mod tests_hover_per_language_directory;
+
+#[path = "./tests/hover_plural.rs"]
+#[cfg(test)]
+mod tests_hover_plural;
+
+#[path = "./tests/hover_namespace.rs"]
+#[cfg(test)]
+mod tests_hover_namespace;
+
+#[path = "./tests/workspace_symbol.rs"]
+#[cfg(test)]
+mod tests_workspace_symbol;
+
+#[path = "./tests/document_symbol.rs"]
+#[cfg(test)]
+mod tests_document_symbol;
+
+#[path = "./tests/translation_file.rs"]
+#[cfg(test)]
+mod tests_translation_file;
+
+#[path = "./tests/diagnostics_untranslated.rs"]
+#[cfg(test)]
+mod tests_diagnostics_untranslated;
+
+#[path = "./tests/diagnostics_markup.rs"]
+#[cfg(test)]
+mod tests_diagnostics_markup;
+
+#[path = "./tests/diagnostics_unused_keys.rs"]
+#[cfg(test)]
+mod tests_diagnostics_unused_keys;
+
+#[path = "./tests/diagnostics_placeholders.rs"]
+#[cfg(test)]
+mod tests_diagnostics_placeholders;
+
+#[path = "./tests/interpolation.rs"]
+#[cfg(test)]
+mod tests_interpolation;
+
+#[path = "./tests/diagnostics_duplicates.rs"]
+#[cfg(test)]
+mod tests_diagnostics_duplicates;
+
+#[path = "./tests/diagnostics_key_naming.rs"]
+#[cfg(test)]
+mod tests_diagnostics_key_naming;
+
+#[path = "./tests/diagnostics_max_length.rs"]
+#[cfg(test)]
+mod tests_diagnostics_max_length;
+
+#[path = "./tests/diagnostics_rules.rs"]
+#[cfg(test)]
+mod tests_diagnostics_rules;
+
+#[path = "./tests/diagnostics_icu.rs"]
+#[cfg(test)]
+mod tests_diagnostics_icu;
+
+#[path = "./tests/diagnostics_syntax_errors.rs"]
+#[cfg(test)]
+mod tests_diagnostics_syntax_errors;
+
 #[path = "./tests/emoji_document.rs"]
 #[cfg(test)]
 mod tests_emoji_document;
 
 mod full_text_document;
-use crate::full_text_document::FullTextDocument;
+use crate::full_text_document::{FullTextDocument, LspTextAdapter};
 
 use lsp_document::apply_change;
-use lsp_document::{IndexedText, TextAdapter, TextMap};
+use lsp_document::{IndexedText, Pos, TextMap};
 
 mod string_helper;
 use crate::string_helper::find_translation_key_by_position;
+use crate::string_helper::find_translation_keys;
+
+mod namespace_helper;
+use namespace_helper::NamespacedKey;
+
+mod check;
+
+mod diagnostic_helper;
+
+mod icu_helper;
+
+mod key_naming_helper;
+
+mod markup_helper;
+
+mod max_length_helper;
+
+mod placeholder_helper;
+use placeholder_helper::PlaceholderSyntax;
+
+mod pull_diagnostics;
+use pull_diagnostics::PullDiagnosticsSupport;
+
+mod plural_helper;
+use plural_helper::{get_plural_category_order, PLURAL_CATEGORIES};
+
 use country_emoji::flag;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
 use std::path::Path;
+use string_helper::find_interpolation_object;
 use string_helper::get_editing_range;
+use string_helper::get_match_score;
+use string_helper::get_next_key_segment;
+use string_helper::get_object_entries;
+use string_helper::get_object_keys;
+use string_helper::get_offset_of_position;
+use string_helper::get_parent_key_length;
+use string_helper::get_value_match_score;
 use string_helper::TRANSLATION_BEGIN_CHARS;
 use string_helper::TRANSLATION_KEY_DIVIDER;
 
@@ -63,7 +185,7 @@ use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer, LspService, Server};
 
 use glob::glob;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::cell::Cell;
 
 use std::path::PathBuf;
@@ -73,7 +195,7 @@ use std::fs;
 
 use itertools::Itertools;
 
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 #[macro_use]
 extern crate derive_new;
@@ -155,9 +277,29 @@ impl TranslationFilesConfig {
 }
 
 #[derive(Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
 struct FileNameConfig {
     #[serde(with = "serde_regex")]
     details: Option<Regex>,
+    /// Like `details`, but matched against the path relative to the workspace folder
+    /// so directories such as `locales/nl/common.json` can be captured
+    #[serde(with = "serde_regex", default)]
+    path_details: Option<Regex>,
+}
+
+/// Returns the path relative to the workspace folder containing it, always using `/` as separator
+fn get_workspace_relative_path(path: &Path, folders: &Vec<WorkspaceFolder>) -> Option<String> {
+    folders.iter().find_map(|folder| {
+        let folder_path = folder.uri.to_file_path().ok()?;
+        let relative_path = path.strip_prefix(folder_path).ok()?;
+
+        Some(
+            relative_path
+                .components()
+                .filter_map(|component| component.as_os_str().to_str())
+                .join("/"),
+        )
+    })
 }
 
 #[derive(Deserialize, Debug, Default, Clone)]
@@ -168,24 +310,365 @@ struct KeyConfig {
     filter: Option<Regex>,
 }
 
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+enum NamespaceSource {
+    /// `locales/en/common.json` has the namespace `common`
+    FileName,
+    /// `locales/common/en.json` has the namespace `common`
+    Directory,
+}
+
+impl Default for NamespaceSource {
+    fn default() -> Self {
+        NamespaceSource::FileName
+    }
+}
+
+fn default_namespace_separator() -> String {
+    ":".to_string()
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct NamespaceConfig {
+    #[serde(default = "default_namespace_separator")]
+    separator: String,
+    #[serde(default)]
+    default: Option<String>,
+    /// Where the namespace is derived from when the file name regex
+    /// does not capture a `namespace` group
+    #[serde(default)]
+    from: NamespaceSource,
+}
+
+impl NamespaceConfig {
+    fn get_namespace_for_path(&self, path: &Path) -> Option<String> {
+        let namespace = match self.from {
+            NamespaceSource::FileName => path.file_stem(),
+            NamespaceSource::Directory => path.parent().and_then(|parent| parent.file_name()),
+        };
+
+        namespace
+            .and_then(OsStr::to_str)
+            .map(|namespace| namespace.to_string())
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+enum ValueSearch {
+    Off,
+    /// Only searches the translations in `preferredLanguage`
+    PreferredLanguage,
+    All,
+}
+
+impl Default for ValueSearch {
+    fn default() -> Self {
+        ValueSearch::All
+    }
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+struct CompletionConfig {
+    /// Only offers the next segment of the keys, like `cart` and `payment` after typing `checkout.`
+    #[serde(default)]
+    hierarchical: bool,
+    /// Language of the translation previewed in the completion item detail
+    #[serde(default)]
+    preferred_language: Option<String>,
+    /// Whether keys can also be found by typing (part of) their translation,
+    /// which makes the client ask for completions again on every keystroke
+    #[serde(default)]
+    value_search: ValueSearch,
+}
+
+fn default_plural_separator() -> String {
+    "_".to_string()
+}
+
+fn default_plural_categories() -> Vec<String> {
+    PLURAL_CATEGORIES
+        .iter()
+        .map(|category| category.to_string())
+        .collect()
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct PluralConfig {
+    /// Separates the key from the plural category,
+    /// `_` for i18next (`item_one`) and `.` for Rails (`item.one`)
+    #[serde(default = "default_plural_separator")]
+    separator: String,
+    #[serde(default = "default_plural_categories")]
+    categories: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+struct UnusedKeysConfig {
+    /// Keys that are only used dynamically, like `status.${status}`
+    #[serde(with = "serde_regex", default)]
+    allow: Vec<Regex>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+struct DiagnosticsConfig {
+    /// The language other translations are compared with, `completion.preferredLanguage` by default
+    #[serde(default)]
+    reference_language: Option<String>,
+    #[serde(default)]
+    unused_keys: UnusedKeysConfig,
+    /// Reports translations that are probably not translated yet, disabled by default
+    #[serde(default)]
+    untranslated: Option<UntranslatedConfig>,
+    /// Reports keys that do not follow the naming rules, disabled by default
+    #[serde(default)]
+    key_naming: Option<KeyNamingConfig>,
+    #[serde(default)]
+    max_length: Vec<MaxLengthRule>,
+    /// The severity per diagnostic code, like `{ "unused-key": "off" }`
+    #[serde(default)]
+    rules: HashMap<String, RuleSeverity>,
+    /// Rules for specific files, later overrides take precedence
+    #[serde(default)]
+    overrides: Vec<DiagnosticsOverride>,
+}
+
+impl DiagnosticsConfig {
+    /// Returns the severity of the diagnostics with `code` in the file at `path`.
+    /// Rules that are not configured keep `default`.
+    fn get_severity(
+        &self,
+        code: &str,
+        path: Option<&Path>,
+        default: Option<DiagnosticSeverity>,
+    ) -> Option<DiagnosticSeverity> {
+        self.overrides
+            .iter()
+            .rev()
+            .filter(|diagnostics_override| {
+                path.map_or(false, |path| diagnostics_override.matches(path))
+            })
+            .find_map(|diagnostics_override| diagnostics_override.rules.get(code))
+            .or_else(|| self.rules.get(code))
+            .map_or(default, |severity| severity.to_diagnostic_severity())
+    }
+
+    /// Whether `code` is configured for any file, used for the rules that are disabled by default
+    fn has_rule(&self, code: &str) -> bool {
+        self.rules.contains_key(code)
+            || self
+                .overrides
+                .iter()
+                .any(|diagnostics_override| diagnostics_override.rules.contains_key(code))
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+enum RuleSeverity {
+    Error,
+    Warning,
+    Info,
+    Hint,
+    Off,
+}
+
+impl RuleSeverity {
+    fn to_diagnostic_severity(self) -> Option<DiagnosticSeverity> {
+        match self {
+            RuleSeverity::Error => Some(DiagnosticSeverity::ERROR),
+            RuleSeverity::Warning => Some(DiagnosticSeverity::WARNING),
+            RuleSeverity::Info => Some(DiagnosticSeverity::INFORMATION),
+            RuleSeverity::Hint => Some(DiagnosticSeverity::HINT),
+            RuleSeverity::Off => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct DiagnosticsOverride {
+    /// Glob patterns of the files, relative patterns match anywhere in the path
+    files: Vec<String>,
+    #[serde(default)]
+    rules: HashMap<String, RuleSeverity>,
+}
+
+impl DiagnosticsOverride {
+    fn matches(&self, path: &Path) -> bool {
+        self.files.iter().any(|pattern| {
+            let pattern = pattern.trim_start_matches("./");
+            let pattern = if pattern.starts_with('/') || pattern.starts_with("**") {
+                pattern.to_string()
+            } else {
+                format!("**/{}", pattern)
+            };
+
+            glob::Pattern::new(&pattern).map_or(false, |pattern| pattern.matches_path(path))
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct MaxLengthRule {
+    /// The keys the rule applies to
+    #[serde(with = "serde_regex")]
+    key: Regex,
+    /// The language the rule applies to, every language when not set
+    #[serde(default)]
+    language: Option<String>,
+    max: usize,
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+struct KeyNamingConfig {
+    /// Every segment of a key has to match, like `^[a-z0-9]+(_[a-z0-9]+)*$` for `snake_case`
+    #[serde(with = "serde_regex", default)]
+    segment: Option<Regex>,
+    /// The maximum number of segments of a key
+    #[serde(default)]
+    max_depth: Option<usize>,
+    /// Characters that may not be used in keys, like a space
+    #[serde(default)]
+    forbidden_characters: String,
+    #[serde(default)]
+    required_prefixes: Vec<RequiredPrefixConfig>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct RequiredPrefixConfig {
+    /// The translation files the prefix is required in, matched against the path
+    #[serde(with = "serde_regex")]
+    file: Regex,
+    prefix: String,
+}
+
+fn default_untranslated_pattern() -> Option<Regex> {
+    Some(Regex::new(r"^\s*$|^(?i:todo|tbd|fixme)\b").unwrap())
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct UntranslatedConfig {
+    /// Values that are stubs instead of translations, empty values and `TODO` by default
+    #[serde(with = "serde_regex", default = "default_untranslated_pattern")]
+    pattern: Option<Regex>,
+    /// Keys that are the same in every language, like brand names
+    #[serde(with = "serde_regex", default)]
+    allow: Vec<Regex>,
+    /// Values that are the same in every language, like `OK`
+    #[serde(with = "serde_regex", default)]
+    allow_values: Vec<Regex>,
+}
+
+fn default_placeholder_syntax() -> Vec<PlaceholderSyntax> {
+    vec![
+        PlaceholderSyntax::I18next,
+        PlaceholderSyntax::Rails,
+        PlaceholderSyntax::Icu,
+    ]
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct PlaceholderConfig {
+    /// The placeholder syntaxes used in the values, `printf` is opt-in
+    /// as it matches regular text like `50% discount`
+    #[serde(default = "default_placeholder_syntax")]
+    syntax: Vec<PlaceholderSyntax>,
+}
+
+impl Default for PlaceholderConfig {
+    fn default() -> Self {
+        PlaceholderConfig {
+            syntax: default_placeholder_syntax(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+enum MessageFormat {
+    /// ICU MessageFormat, like `{count, plural, one {# item} other {# items}}`
+    Icu,
+}
+
 #[derive(Deserialize, Debug, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ExtensionConfig {
     translation_files: TranslationFilesConfig,
+    /// The source files that are scanned for usages of translation keys
+    #[serde(default)]
+    source_files: Option<TranslationFilesConfig>,
     file_name: FileNameConfig,
     #[serde(default)]
     key: KeyConfig,
+    #[serde(default)]
+    namespace: Option<NamespaceConfig>,
+    #[serde(default)]
+    plural: Option<PluralConfig>,
+    #[serde(default)]
+    completion: CompletionConfig,
+    #[serde(default)]
+    placeholders: PlaceholderConfig,
+    /// The syntax of the values, which is validated and expanded in hovers
+    #[serde(default)]
+    message_format: Option<MessageFormat>,
+    #[serde(default)]
+    diagnostics: DiagnosticsConfig,
+}
+
+impl ExtensionConfig {
+    fn get_reference_language(&self) -> Option<&String> {
+        self.diagnostics
+            .reference_language
+            .as_ref()
+            .or_else(|| self.completion.preferred_language.as_ref())
+    }
 }
 
-#[derive(new)]
+#[derive(new, Clone)]
 pub struct Backend {
     client: Client,
     #[new(value = "Arc::new(Mutex::new(Cell::new(vec![])))")]
     definitions: Arc<Mutex<Cell<Vec<Definition>>>>,
+    #[new(value = "Arc::new(Mutex::new(Cell::new(vec![])))")]
+    arb_metadata: Arc<Mutex<Cell<Vec<Definition>>>>,
     #[new(value = "Arc::new(Mutex::new(Cell::new(ExtensionConfig::default())))")]
     config: Arc<Mutex<Cell<ExtensionConfig>>>,
     #[new(value = "Arc::new(Mutex::new(Cell::new(vec![])))")]
     documents: Arc<Mutex<Cell<Vec<FullTextDocument>>>>,
+    #[new(value = "Arc::new(Mutex::new(Cell::new(vec![])))")]
+    workspace_folders: Arc<Mutex<Cell<Vec<WorkspaceFolder>>>>,
+    #[new(value = "Arc::new(Mutex::new(Cell::new(vec![])))")]
+    translation_files: Arc<Mutex<Cell<Vec<PathBuf>>>>,
+    #[new(value = "Arc::new(Mutex::new(Cell::new(vec![])))")]
+    source_files: Arc<Mutex<Cell<Vec<PathBuf>>>>,
+    #[new(value = "Arc::new(Mutex::new(Cell::new(vec![])))")]
+    usages: Arc<Mutex<Cell<Vec<Usage>>>>,
+    #[new(value = "Arc::new(Mutex::new(Cell::new(vec![])))")]
+    syntax_errors: Arc<Mutex<Cell<Vec<(PathBuf, Diagnostic)>>>>,
+    #[new(value = "Arc::new(Mutex::new(Cell::new(PullDiagnosticsSupport::default())))")]
+    pull_diagnostics: Arc<Mutex<Cell<PullDiagnosticsSupport>>>,
+    #[new(value = "Arc::new(Mutex::new(Cell::new(PendingDiagnostics::default())))")]
+    pending_diagnostics: Arc<Mutex<Cell<PendingDiagnostics>>>,
+}
+
+/// Time without further changes before the diagnostics of changed documents are published
+const DIAGNOSTICS_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Changes whose diagnostics are published once no further changes follow
+#[derive(Default)]
+struct PendingDiagnostics {
+    /// Increased on every change, so only the last scheduled publish runs
+    generation: usize,
+    documents: Vec<Url>,
+    /// Whether the used keys changed, which affects the diagnostics of the translation files
+    translation_files: bool,
 }
 
 use std::ffi::OsStr;
@@ -205,93 +688,804 @@ impl std::error::Error for InvalidTranslationFileStructure {}
 
 impl Backend {
     /// Figures out which translation files exists on the system of the user
-    /// and calls `read_translation` to append them to the definitions
+    /// and calls `load_workspace` to read them
     async fn fetch_translations(&self, config_value: Value) {
-        // TODO: Move setting config to other function
         let new_config: ExtensionConfig = serde_json::from_value(config_value).unwrap();
-        self.config.lock().unwrap().set(new_config.clone());
 
         let folders = self.client.workspace_folders().await.unwrap().unwrap();
 
         self.client
-            .log_message(MessageType::Info, format!("Workspace folders: {:?}", folders))
+            .log_message(MessageType::INFO, format!("Workspace folders: {:?}", folders))
             .await;
 
-        let files: Vec<PathBuf> = self
-            .config
-            .lock()
-            .unwrap()
-            .get_mut()
+        self.register_file_watch_capability(&new_config, &folders)
+            .await;
+
+        for (message_type, message) in self.load_workspace(new_config, &folders) {
+            self.client.log_message(message_type, message).await;
+        }
+
+        self.publish_diagnostics().await;
+    }
+
+    /// Sets the config and reads the translation files and source files of `folders`
+    /// with `read_translation` and `fetch_usages`.
+    /// Returns the messages to log, so `check` can leave out the ones that are not errors.
+    fn load_workspace(
+        &self,
+        new_config: ExtensionConfig,
+        folders: &Vec<WorkspaceFolder>,
+    ) -> Vec<(MessageType, String)> {
+        let files: Vec<PathBuf> = new_config
             .translation_files
-            .get_translation_files_from_config(&folders);
+            .get_translation_files_from_config(folders);
 
-        eprintln!("Translation files: {:?}", files);
+        let mut messages = vec![(MessageType::INFO, format!("Translation files: {:?}", files))];
 
-        self.register_file_watch_capability(&new_config, &folders)
-            .await;
+        self.config.lock().unwrap().set(new_config.clone());
+        self.workspace_folders.lock().unwrap().set(folders.clone());
+        self.translation_files.lock().unwrap().set(files.clone());
 
         // Clear and add definitions
         self.definitions.lock().unwrap().set(vec![]);
+        self.arb_metadata.lock().unwrap().set(vec![]);
+        self.syntax_errors.lock().unwrap().set(vec![]);
+
+        for file in files.iter() {
+            messages.push(match self.read_translation(file, folders) {
+                Ok(_) => (
+                    MessageType::INFO,
+                    format!("Loaded definitions from {:?}", file),
+                ),
+                Err(err) => (
+                    MessageType::ERROR,
+                    format!("Could not read translation file {:?}: {:?}", file, err),
+                ),
+            });
+        }
 
-        // TODO: Use self.client.log_message instead of eprintln!
-        files.iter().for_each(|file| {
-            match self.read_translation(file) {
-                // TODO: Print this to VSCode
-                Ok(_) => {
-                    eprintln!("Loaded definitions from {:?}", file);
+        messages.append(&mut self.fetch_usages(&new_config, folders));
+        messages
+    }
 
-                    /* self.client
-                    .log_message(MessageType::Info, format!("folders: {:?}", folders)).await; */
+    /// Checks the translation files and publishes the problems per file,
+    /// which also clears the problems of files that no longer have any
+    async fn publish_diagnostics(&self) {
+        let pull_diagnostics = self.pull_diagnostics.lock().unwrap().get();
+        if pull_diagnostics.enabled {
+            // The client pulls the diagnostics itself and only has to know that they changed
+            if pull_diagnostics.refresh {
+                if let Err(err) = self
+                    .client
+                    .send_request::<request::WorkspaceDiagnosticRefresh>(())
+                    .await
+                {
+                    eprintln!("Could not refresh diagnostics: {:?}", err);
                 }
-                Err(err) => {
-                    eprintln!("Could not read translation file {:?}.", file);
-                    eprintln!("{:?}", err);
+            }
 
-                    /* self.client
-                    .log_message(MessageType::Info, format!("folders: {:?}", folders)).await; */
-                }
+            return;
+        }
+
+        self.publish_translation_file_diagnostics().await;
+
+        let documents = self.documents.lock().unwrap().get_mut().clone();
+        for document in documents {
+            if self.get_translation_file_path(&document.uri).is_none() {
+                let document_diagnostics = self.get_document_diagnostics(&document);
+
+                self.client
+                    .publish_diagnostics(document.uri, document_diagnostics, None)
+                    .await;
             }
-        });
+        }
     }
 
-    /// Register capability to watch files
-    async fn register_file_watch_capability(
-        &self,
-        config: &ExtensionConfig,
-        folders: &Vec<WorkspaceFolder>,
-    ) -> Result<(), Error> {
-        let watched_patterns = DidChangeWatchedFilesRegistrationOptions {
-            watchers: folders
-                .iter()
-                .map(|folder| {
-                    config
-                        .translation_files
-                        .include
-                        .iter()
-                        .map(|pattern| FileSystemWatcher {
-                            glob_pattern: path_clean::clean(
-                                folder
-                                    .uri
-                                    .to_file_path()
-                                    .unwrap()
-                                    .join(PathBuf::from(pattern))
-                                    .to_str()
-                                    .unwrap(),
-                            ),
-                            kind: None,
-                        })
-                        .collect::<Vec<FileSystemWatcher>>()
-                })
-                .flatten()
-                .collect(),
-        };
+    async fn publish_translation_file_diagnostics(&self) {
+        let mut diagnostics = self.get_diagnostics();
 
-        self.client
-            .register_capability(vec![Registration {
-                id: "workspace/didChangeWatchedFiles".to_string(),
-                method: "workspace/didChangeWatchedFiles".to_string(),
-                register_options: Some(serde_json::to_value(watched_patterns).unwrap()),
-            }])
+        let files = self.translation_files.lock().unwrap().get_mut().clone();
+        for file in files {
+            if let Ok(uri) = Url::from_file_path(&file) {
+                self.client
+                    .publish_diagnostics(uri, diagnostics.remove(&file).unwrap_or_default(), None)
+                    .await;
+            }
+        }
+    }
+
+    /// Checks a translation file or source file, which does not have to be open
+    fn get_file_diagnostics(&self, uri: &Url) -> Vec<Diagnostic> {
+        match self.get_translation_file_path(uri) {
+            Some(path) => self.get_diagnostics().remove(&path).unwrap_or_default(),
+            None => self
+                .get_source_document(uri)
+                .map(|document| self.get_document_diagnostics(&document))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Checks every translation file and every source file with translation calls
+    fn get_workspace_diagnostics(&self) -> Vec<(Url, Vec<Diagnostic>)> {
+        let mut diagnostics = self.get_diagnostics();
+
+        let files = self.translation_files.lock().unwrap().get_mut().clone();
+        let mut workspace_diagnostics = files
+            .iter()
+            .filter_map(|file| {
+                let uri = Url::from_file_path(file).ok()?;
+                Some((uri, diagnostics.remove(file).unwrap_or_default()))
+            })
+            .collect::<Vec<_>>();
+
+        let usage_uris = self
+            .usages
+            .lock()
+            .unwrap()
+            .get_mut()
+            .iter()
+            .map(|usage| usage.location.uri.clone())
+            .collect::<Vec<_>>();
+        let document_uris = self
+            .documents
+            .lock()
+            .unwrap()
+            .get_mut()
+            .iter()
+            .map(|document| document.uri.clone())
+            .collect::<Vec<_>>();
+
+        workspace_diagnostics.extend(
+            usage_uris
+                .into_iter()
+                .chain(document_uris)
+                .unique()
+                .filter(|uri| self.get_translation_file_path(uri).is_none())
+                .filter_map(|uri| {
+                    let document = self.get_source_document(&uri)?;
+                    Some((uri, self.get_document_diagnostics(&document)))
+                }),
+        );
+
+        workspace_diagnostics
+    }
+
+    /// Returns the open document at `uri` or reads it from the disk
+    fn get_source_document(&self, uri: &Url) -> Option<FullTextDocument> {
+        let document = self
+            .documents
+            .lock()
+            .unwrap()
+            .get_mut()
+            .iter()
+            .find(|document| document.uri == *uri)
+            .cloned();
+
+        document.or_else(|| {
+            let text = fs::read_to_string(uri.to_file_path().ok()?).ok()?;
+            Some(FullTextDocument::new(uri.clone(), String::new(), 0, text))
+        })
+    }
+
+    fn get_document_version(&self, uri: &Url) -> Option<i64> {
+        self.documents
+            .lock()
+            .unwrap()
+            .get_mut()
+            .iter()
+            .find(|document| document.uri == *uri)
+            .map(|document| document.version)
+    }
+
+    /// Publishes the diagnostics of a changed document after `DIAGNOSTICS_DEBOUNCE`,
+    /// together with the translation files if `translation_files` is set
+    fn schedule_diagnostics(&self, uri: Url, translation_files: bool) {
+        let generation = {
+            let mut pending = self.pending_diagnostics.lock().unwrap();
+            let pending = pending.get_mut();
+
+            pending.generation += 1;
+            pending.translation_files |= translation_files;
+            if !pending.documents.contains(&uri) {
+                pending.documents.push(uri);
+            }
+
+            pending.generation
+        };
+
+        let backend = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(DIAGNOSTICS_DEBOUNCE).await;
+
+            let pending = {
+                let mut pending = backend.pending_diagnostics.lock().unwrap();
+                if pending.get_mut().generation != generation {
+                    return;
+                }
+
+                std::mem::replace(
+                    pending.get_mut(),
+                    PendingDiagnostics {
+                        generation,
+                        ..Default::default()
+                    },
+                )
+            };
+
+            backend.publish_pending_diagnostics(pending).await;
+        });
+    }
+
+    async fn publish_pending_diagnostics(&self, pending: PendingDiagnostics) {
+        if self.pull_diagnostics.lock().unwrap().get().enabled {
+            // The client pulls changed documents itself
+            if pending.translation_files {
+                self.publish_diagnostics().await;
+            }
+            return;
+        }
+
+        if pending.translation_files {
+            self.publish_translation_file_diagnostics().await;
+        }
+
+        // Changed translation files can change the diagnostics of every source document
+        let definitions_changed = pending
+            .documents
+            .iter()
+            .any(|uri| self.get_translation_file_path(uri).is_some());
+
+        let documents = self.documents.lock().unwrap().get_mut().clone();
+        for document in documents {
+            if self.get_translation_file_path(&document.uri).is_none()
+                && (definitions_changed || pending.documents.contains(&document.uri))
+            {
+                let document_diagnostics = self.get_document_diagnostics(&document);
+
+                self.client
+                    .publish_diagnostics(document.uri, document_diagnostics, None)
+                    .await;
+            }
+        }
+    }
+
+    /// Returns the keys used in the document at `uri`
+    fn get_used_keys(&self, uri: &Url) -> HashSet<NamespacedKey> {
+        self.usages
+            .lock()
+            .unwrap()
+            .get_mut()
+            .iter()
+            .filter(|usage| usage.location.uri == *uri)
+            .map(|usage| usage.key.clone())
+            .collect()
+    }
+
+    /// Checks the translation calls in a source document
+    fn get_document_diagnostics(&self, document: &FullTextDocument) -> Vec<Diagnostic> {
+        let text = document.text.text();
+
+        let mut diagnostics = find_translation_keys(text)
+            .into_iter()
+            .filter_map(|translation_key| {
+                let key = self.resolve_key(translation_key.as_str(), text, translation_key.start());
+                let placeholder_names = self.get_placeholder_names(&key);
+
+                // Spread objects may pass any placeholder
+                let passed_names = match find_interpolation_object(text, translation_key.end()) {
+                    Some(body) => get_object_keys(&text[body])?,
+                    None => vec![],
+                };
+
+                let missing_names = placeholder_names
+                    .into_iter()
+                    .filter(|name| !passed_names.contains(name))
+                    .collect::<Vec<String>>();
+
+                if missing_names.is_empty() {
+                    return None;
+                }
+
+                let range = document
+                    .text
+                    .offset_range_to_range(translation_key.range())?;
+
+                Some(diagnostic_helper::create_missing_placeholder_diagnostic(
+                    tower_lsp::lsp_types::Range::new(
+                        document.text.pos_to_lsp_pos(&range.start)?,
+                        document.text.pos_to_lsp_pos(&range.end)?,
+                    ),
+                    translation_key.as_str(),
+                    &missing_names,
+                ))
+            })
+            .collect::<Vec<Diagnostic>>();
+
+        let config = self.config.lock().unwrap().get_mut().clone();
+
+        if let Some(key_naming_config) = config.diagnostics.key_naming.as_ref() {
+            diagnostics.extend(
+                self.usages
+                    .lock()
+                    .unwrap()
+                    .get_mut()
+                    .iter()
+                    .filter(|usage| usage.location.uri == document.uri)
+                    .filter_map(|usage| {
+                        diagnostic_helper::create_key_naming_diagnostic(
+                            usage.location.range,
+                            &usage.key,
+                            key_naming_config,
+                        )
+                    }),
+            );
+        }
+
+        if config
+            .diagnostics
+            .has_rule(diagnostic_helper::MISSING_KEY_CODE)
+        {
+            let usages = self.usages.lock().unwrap().get_mut().clone();
+            let mut definitions = self.definitions.lock().unwrap();
+            let definitions = definitions.get_mut();
+
+            diagnostics.extend(
+                usages
+                    .iter()
+                    .filter(|usage| usage.location.uri == document.uri)
+                    .filter(|usage| {
+                        !definitions
+                            .iter()
+                            .any(|definition| definition == &usage.key)
+                    })
+                    .map(|usage| {
+                        diagnostic_helper::create_missing_key_diagnostic(
+                            usage.location.range,
+                            &usage.key.key,
+                        )
+                    }),
+            );
+        }
+
+        diagnostic_helper::configure_diagnostics(
+            diagnostics,
+            document.uri.to_file_path().ok().as_deref(),
+            Some(text),
+            &config.diagnostics,
+        )
+    }
+
+    /// Returns the names that have to be passed for the placeholders in the translations of `key`
+    fn get_placeholder_names(&self, key: &NamespacedKey) -> Vec<String> {
+        let syntaxes = self
+            .config
+            .lock()
+            .unwrap()
+            .get_mut()
+            .placeholders
+            .syntax
+            .clone();
+
+        let mut definitions = self.definitions.lock().unwrap();
+
+        definitions
+            .get_mut()
+            .iter()
+            .filter(|definition| *definition == key)
+            .flat_map(|definition| {
+                placeholder_helper::get_placeholders(&definition.value, &syntaxes)
+            })
+            // Positional placeholders are not passed by name
+            .filter(|placeholder| placeholder.syntax != PlaceholderSyntax::Printf)
+            .filter_map(|placeholder| {
+                // `{{user.name}}` is filled by passing `user`
+                let name = placeholder.name.split('.').next()?.to_string();
+                if name.chars().all(|char| char.is_ascii_digit()) {
+                    None
+                } else {
+                    Some(name)
+                }
+            })
+            .unique()
+            .collect()
+    }
+
+    /// Suggests the placeholders of a key while typing the object passed after it,
+    /// or `None` when the position is not in such an object
+    fn get_interpolation_completions(
+        &self,
+        document: &FullTextDocument,
+        pos: &Pos,
+    ) -> Option<Vec<CompletionItem>> {
+        let text = document.text.text();
+        let offset = get_offset_of_position(&document.text, pos);
+
+        let (translation_key, body) = find_translation_keys(text)
+            .into_iter()
+            .filter_map(|translation_key| {
+                Some((
+                    translation_key,
+                    find_interpolation_object(text, translation_key.end())?,
+                ))
+            })
+            .find(|(_, body)| body.start <= offset && offset <= body.end)?;
+
+        // Only the names are completed, not the values or nested objects
+        let (entries, depth) = get_object_entries(&text[body.start..offset]);
+        if depth > 0 || entries.last().map_or(false, |entry| entry.contains(':')) {
+            return Some(vec![]);
+        }
+
+        let passed_names = entries[..entries.len() - 1]
+            .iter()
+            .map(|entry| entry.split(':').next().unwrap_or("").trim())
+            .collect::<Vec<&str>>();
+
+        let key = self.resolve_key(translation_key.as_str(), text, translation_key.start());
+
+        Some(
+            self.get_placeholder_names(&key)
+                .into_iter()
+                .filter(|name| !passed_names.contains(&name.as_str()))
+                .map(|name| CompletionItem {
+                    label: name,
+                    kind: Some(CompletionItemKind::VARIABLE),
+                    detail: Some(format!("Placeholder of {}", translation_key.as_str())),
+                    ..Default::default()
+                })
+                .collect(),
+        )
+    }
+
+    fn get_diagnostics(&self) -> HashMap<PathBuf, Vec<Diagnostic>> {
+        let config = self.config.lock().unwrap().get_mut().clone();
+        let mut definitions = self.definitions.lock().unwrap();
+        let definitions = definitions.get_mut();
+
+        let mut diagnostics = self.syntax_errors.lock().unwrap().get_mut().clone();
+
+        // Without source files every key would be reported as unused
+        if config.source_files.is_some() {
+            diagnostics.append(&mut diagnostic_helper::get_unused_key_diagnostics(
+                definitions,
+                self.usages.lock().unwrap().get_mut(),
+                &config.diagnostics.unused_keys,
+            ));
+        }
+
+        diagnostics.append(&mut diagnostic_helper::get_duplicate_key_diagnostics(
+            definitions,
+        ));
+
+        if config.message_format == Some(MessageFormat::Icu) {
+            diagnostics.append(&mut diagnostic_helper::get_icu_diagnostics(definitions));
+        }
+
+        if let Some(key_naming_config) = config.diagnostics.key_naming.as_ref() {
+            diagnostics.append(&mut diagnostic_helper::get_key_naming_diagnostics(
+                definitions,
+                key_naming_config,
+            ));
+        }
+
+        diagnostics.append(&mut diagnostic_helper::get_max_length_diagnostics(
+            definitions,
+            self.arb_metadata.lock().unwrap().get_mut(),
+            &config.diagnostics.max_length,
+        ));
+
+        if let Some(untranslated_config) = config.diagnostics.untranslated.as_ref() {
+            diagnostics.append(&mut diagnostic_helper::get_untranslated_diagnostics(
+                definitions,
+                config
+                    .get_reference_language()
+                    .map(|language| language.as_str()),
+                untranslated_config,
+                &config.placeholders.syntax,
+            ));
+        }
+
+        diagnostics.append(&mut diagnostic_helper::get_markup_diagnostics(
+            definitions,
+            config
+                .get_reference_language()
+                .map(|language| language.as_str()),
+        ));
+
+        if let Some(reference_language) = config.get_reference_language() {
+            diagnostics.append(&mut diagnostic_helper::get_placeholder_diagnostics(
+                definitions,
+                reference_language,
+                &config.placeholders.syntax,
+            ));
+        }
+
+        if config
+            .diagnostics
+            .has_rule(diagnostic_helper::MISSING_LANGUAGE_CODE)
+        {
+            diagnostics.append(&mut diagnostic_helper::get_missing_language_diagnostics(
+                definitions,
+            ));
+        }
+
+        diagnostics
+            .into_iter()
+            .into_group_map()
+            .into_iter()
+            .map(|(path, diagnostics)| {
+                let text = self.get_file_text(&path);
+                let diagnostics = diagnostic_helper::configure_diagnostics(
+                    diagnostics,
+                    Some(&path),
+                    text.as_deref(),
+                    &config.diagnostics,
+                );
+
+                (path, diagnostics)
+            })
+            .collect()
+    }
+
+    /// Returns the text of a file, preferring the open document as it may contain unsaved changes
+    fn get_file_text(&self, path: &Path) -> Option<String> {
+        let uri = Url::from_file_path(path).ok()?;
+
+        self.documents
+            .lock()
+            .unwrap()
+            .get_mut()
+            .iter()
+            .find(|document| document.uri == uri)
+            .map(|document| document.text.text().to_string())
+            .or_else(|| fs::read_to_string(path).ok())
+    }
+
+    /// Creates an edit that renames `key` to follow the naming rules in every translation file
+    /// and source file, or `None` when that cannot be done mechanically, e.g. when a parent
+    /// object of the key in a translation file would have to be renamed as well
+    fn get_rename_key_edit(&self, key: &NamespacedKey) -> Option<(String, WorkspaceEdit)> {
+        let config = self.config.lock().unwrap().get_mut().clone();
+        let key_naming_config = config.diagnostics.key_naming.as_ref()?;
+        let namespace_separator = config
+            .namespace
+            .as_ref()
+            .map(|namespace| namespace.separator.as_str());
+
+        let renamed_key = key_naming_helper::get_renamed_key(&key.key, None, key_naming_config)?;
+
+        let definitions = self
+            .definitions
+            .lock()
+            .unwrap()
+            .get_mut()
+            .iter()
+            .filter(|definition| *definition == key)
+            .filter_map(|definition| {
+                Some((
+                    definition.file.as_ref()?.path.clone(),
+                    definition.key_range?,
+                    definition.get_identifier().clone(),
+                ))
+            })
+            .collect::<Vec<(PathBuf, tower_lsp::lsp_types::Range, String)>>();
+
+        let usages = self
+            .usages
+            .lock()
+            .unwrap()
+            .get_mut()
+            .iter()
+            .filter(|usage| usage.key == *key)
+            .map(|usage| usage.location.clone())
+            .collect::<Vec<Location>>();
+
+        let get_text = |path: &Path, range: tower_lsp::lsp_types::Range| {
+            let text = self.get_file_text(path)?;
+            let start = tree_sitter_helper::get_offset_of_lsp_position(&text, range.start)?;
+            let end = tree_sitter_helper::get_offset_of_lsp_position(&text, range.end)?;
+
+            text.get(start..end).map(String::from)
+        };
+
+        let mut changes = HashMap::<Url, Vec<TextEdit>>::new();
+
+        for (path, key_range, identifier) in definitions {
+            // Nested translation files only contain the last segments of the key at its range
+            let written_key = get_text(&path, key_range)?;
+            let unquoted_key = written_key.trim_matches(|char| char == '"' || char == '\'');
+            let parent_key = identifier.strip_suffix(unquoted_key)?;
+            if !parent_key.is_empty() && !parent_key.ends_with(TRANSLATION_KEY_DIVIDER) {
+                return None;
+            }
+
+            let new_text =
+                key_naming_helper::get_renamed_key(&written_key, None, key_naming_config)?;
+            let renamed_written_key = new_text.trim_matches(|char| char == '"' || char == '\'');
+            if format!("{}{}", parent_key, renamed_written_key) != renamed_key {
+                return None;
+            }
+
+            changes
+                .entry(Url::from_file_path(&path).ok()?)
+                .or_default()
+                .push(TextEdit::new(key_range, new_text));
+        }
+
+        for location in usages {
+            let written_key = get_text(&location.uri.to_file_path().ok()?, location.range)?;
+            let new_text = key_naming_helper::get_renamed_key(
+                &written_key,
+                namespace_separator,
+                key_naming_config,
+            )?;
+
+            changes
+                .entry(location.uri)
+                .or_default()
+                .push(TextEdit::new(location.range, new_text));
+        }
+
+        Some((renamed_key, WorkspaceEdit::new(changes)))
+    }
+
+    /// Creates an edit that removes `key` from every translation file it is defined in
+    fn get_remove_key_edit(&self, key: &NamespacedKey) -> WorkspaceEdit {
+        let mut changes = HashMap::<Url, Vec<TextEdit>>::new();
+
+        let locations = self
+            .definitions
+            .lock()
+            .unwrap()
+            .get_mut()
+            .iter()
+            .filter(|definition| *definition == key)
+            .filter_map(|definition| {
+                Some((
+                    definition.file.as_ref()?.path.clone(),
+                    definition.key_range?,
+                ))
+            })
+            .collect::<Vec<(PathBuf, tower_lsp::lsp_types::Range)>>();
+
+        for (path, key_range) in locations {
+            let language = match path
+                .extension()
+                .and_then(OsStr::to_str)
+                .and_then(tree_sitter_helper::get_language_by_extension)
+            {
+                Some(language) => language,
+                None => continue,
+            };
+
+            let range = self.get_file_text(&path).and_then(|text| {
+                tree_sitter_helper::get_removal_range_for_key(&text, language, key_range.start)
+            });
+
+            if let (Some(range), Ok(uri)) = (range, Url::from_file_path(&path)) {
+                changes.entry(uri).or_default().push(TextEdit {
+                    range,
+                    new_text: String::new(),
+                });
+            }
+        }
+
+        WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }
+    }
+
+    /// Scans the source files of the user for translation keys to know where they are used
+    fn fetch_usages(
+        &self,
+        config: &ExtensionConfig,
+        folders: &Vec<WorkspaceFolder>,
+    ) -> Vec<(MessageType, String)> {
+        self.usages.lock().unwrap().set(vec![]);
+        let mut messages = vec![];
+
+        let files = match config.source_files.as_ref() {
+            Some(source_files) => source_files.get_translation_files_from_config(folders),
+            None => vec![],
+        };
+        self.source_files.lock().unwrap().set(files.clone());
+
+        for file in files {
+            let text = match fs::read_to_string(&file) {
+                Ok(text) => text,
+                Err(err) => {
+                    messages.push((
+                        MessageType::ERROR,
+                        format!("Could not read source file {:?}: {:?}", file, err),
+                    ));
+                    continue;
+                }
+            };
+
+            if let Ok(uri) = Url::from_file_path(&file) {
+                self.index_usages(&uri, &IndexedText::new(text));
+            }
+        }
+
+        // Open documents may have unsaved changes
+        let documents = self.documents.lock().unwrap().get_mut().clone();
+        for document in documents {
+            self.index_usages(&document.uri, &document.text);
+        }
+
+        messages
+    }
+
+    /// Replaces the usages of the document at `uri` with the keys in `text`
+    fn index_usages(&self, uri: &Url, text: &IndexedText<String>) {
+        let new_usages = find_translation_keys(text.text())
+            .into_iter()
+            .filter_map(|translation_key| {
+                let range = text.offset_range_to_range(translation_key.range())?;
+
+                Some(Usage {
+                    key: self.resolve_key(
+                        translation_key.as_str(),
+                        text.text(),
+                        translation_key.start(),
+                    ),
+                    location: Location::new(
+                        uri.clone(),
+                        tower_lsp::lsp_types::Range::new(
+                            text.pos_to_lsp_pos(&range.start)?,
+                            text.pos_to_lsp_pos(&range.end)?,
+                        ),
+                    ),
+                })
+            })
+            .collect::<Vec<Usage>>();
+
+        let mut usages = self.usages.lock().unwrap();
+        let usages = usages.get_mut();
+
+        usages.retain(|usage| &usage.location.uri != uri);
+        usages.extend(new_usages);
+    }
+
+    /// Register capability to watch files
+    async fn register_file_watch_capability(
+        &self,
+        config: &ExtensionConfig,
+        folders: &Vec<WorkspaceFolder>,
+    ) -> Result<(), Error> {
+        let watched_patterns = DidChangeWatchedFilesRegistrationOptions {
+            watchers: folders
+                .iter()
+                .map(|folder| {
+                    config
+                        .translation_files
+                        .include
+                        .iter()
+                        .map(|pattern| FileSystemWatcher {
+                            glob_pattern: GlobPattern::String(path_clean::clean(
+                                folder
+                                    .uri
+                                    .to_file_path()
+                                    .unwrap()
+                                    .join(PathBuf::from(pattern))
+                                    .to_str()
+                                    .unwrap(),
+                            )),
+                            kind: None,
+                        })
+                        .collect::<Vec<FileSystemWatcher>>()
+                })
+                .flatten()
+                .collect(),
+        };
+
+        self.client
+            .register_capability(vec![Registration {
+                id: "workspace/didChangeWatchedFiles".to_string(),
+                method: "workspace/didChangeWatchedFiles".to_string(),
+                register_options: Some(serde_json::to_value(watched_patterns).unwrap()),
+            }])
             .await
     }
 
@@ -311,9 +1505,58 @@ impl Backend {
     }
 
     /// Reads the translations from a single file and adds them to the `definitions`
-    fn read_translation(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    fn read_translation(
+        &self,
+        path: &Path,
+        folders: &Vec<WorkspaceFolder>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         let file = fs::read_to_string(path)?;
 
+        self.add_translation(path, file, folders)
+    }
+
+    /// Replaces the translations of the file at `path` with the ones in `text`,
+    /// like the unsaved content of an opened translation file
+    fn reload_translation(
+        &self,
+        path: &Path,
+        text: String,
+        folders: &Vec<WorkspaceFolder>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let is_other_file = |definition: &Definition| {
+            definition
+                .file
+                .as_ref()
+                .map_or(true, |file| file.path != path)
+        };
+
+        self.definitions
+            .lock()
+            .unwrap()
+            .get_mut()
+            .retain(is_other_file);
+        self.arb_metadata
+            .lock()
+            .unwrap()
+            .get_mut()
+            .retain(is_other_file);
+        self.syntax_errors
+            .lock()
+            .unwrap()
+            .get_mut()
+            .retain(|(error_path, _)| error_path != path);
+
+        self.add_translation(path, text, folders)
+    }
+
+    /// Parses the translations in `file`, the content of the translation file at `path`,
+    /// and adds them to the `definitions`
+    fn add_translation(
+        &self,
+        path: &Path,
+        file: String,
+        folders: &Vec<WorkspaceFolder>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         let ext = path.extension().and_then(OsStr::to_str);
         if ext.is_none() {
             return Err(Box::new(InvalidTranslationFileStructure));
@@ -324,75 +1567,315 @@ impl Backend {
             return Err(Box::new(InvalidTranslationFileStructure));
         }
 
-        let query_source = tree_sitter_helper::get_query_source_by_language(ext.unwrap());
-        if query_source.is_none() {
-            return Err(Box::new(InvalidTranslationFileStructure));
-        }
+        let query_source = tree_sitter_helper::get_query_source_by_language(ext.unwrap());
+        if query_source.is_none() {
+            return Err(Box::new(InvalidTranslationFileStructure));
+        }
+
+        let (mut new_definitions, errors) = tree_sitter_helper::parse_translation_structure(
+            file,
+            self.config.lock().unwrap().get_mut(),
+            language.unwrap(),
+            query_source.unwrap(),
+        );
+
+        // Use file regex language for all above definitions
+        let mut extra_data = HashMap::<String, String>::new();
+
+        // Use path regex details for all above definitions
+        if let Some(path_details_regex) = self
+            .config
+            .lock()
+            .unwrap()
+            .get_mut()
+            .file_name
+            .path_details
+            .as_ref()
+        {
+            let relative_path = get_workspace_relative_path(path, folders);
+
+            if let Some(cap) = relative_path
+                .as_ref()
+                .and_then(|relative_path| path_details_regex.captures(relative_path))
+            {
+                for capture_group_name in path_details_regex.capture_names().flatten() {
+                    if let Some(capture_group_result) = cap.name(capture_group_name) {
+                        extra_data.insert(
+                            capture_group_name.to_string(),
+                            capture_group_result.as_str().to_string(),
+                        );
+                    }
+                }
+            };
+        };
+
+        // Use file regex language for all above definitions
+        if let Some(file_name_details_regex) = self
+            .config
+            .lock()
+            .unwrap()
+            .get_mut()
+            .file_name
+            .details
+            .as_ref()
+        {
+            if let Some(cap) =
+                file_name_details_regex.captures(path.file_name().unwrap().to_str().unwrap())
+            {
+                for capture_group_name in file_name_details_regex.capture_names().flatten() {
+                    let capture_group_result = cap.name(capture_group_name);
+
+                    if capture_group_result.is_some() {
+                        extra_data.insert(
+                            capture_group_name.to_string(),
+                            capture_group_result.unwrap().as_str().to_string(),
+                        );
+                    }
+                }
+            };
+        };
+
+        if !extra_data.contains_key("namespace") {
+            if let Some(namespace) = self
+                .config
+                .lock()
+                .unwrap()
+                .get_mut()
+                .namespace
+                .as_ref()
+                .and_then(|namespace_config| namespace_config.get_namespace_for_path(path))
+            {
+                extra_data.insert("namespace".to_string(), namespace);
+            }
+        }
+
+        let translation_file = DefinitionSource {
+            path: path.to_path_buf(),
+            extra_data,
+        };
+
+        for definition in new_definitions.iter_mut() {
+            definition.file = Some(translation_file.clone());
+        }
+
+        // The metadata of ARB files is only used for the maximum lengths of the translations
+        if ext == Some("arb") {
+            let (mut metadata, translations): (Vec<Definition>, Vec<Definition>) = new_definitions
+                .into_iter()
+                .partition(|definition| max_length_helper::is_arb_metadata_key(&definition.key));
+
+            self.arb_metadata
+                .lock()
+                .unwrap()
+                .get_mut()
+                .append(&mut metadata);
+            new_definitions = translations;
+        }
+
+        let mut definitions = self.definitions.lock().unwrap();
+        new_definitions.append(definitions.get_mut());
+        definitions.set(new_definitions);
+
+        self.syntax_errors.lock().unwrap().get_mut().append(
+            &mut diagnostic_helper::get_syntax_error_diagnostics(path, errors),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the path of the document if it is one of the translation files in the config
+    fn get_translation_file_path(&self, uri: &Url) -> Option<PathBuf> {
+        let path = uri.to_file_path().ok()?;
+
+        self.translation_files
+            .lock()
+            .unwrap()
+            .get_mut()
+            .contains(&path)
+            .then(|| path)
+    }
+
+    /// Returns the definition of the translation file at `path` whose key or value
+    /// contains `position`
+    fn get_translation_file_definition_at(
+        &self,
+        path: &Path,
+        position: Position,
+    ) -> Option<NamespacedKey> {
+        let mut definitions = self.definitions.lock().unwrap();
+
+        let contains = |range: Option<tower_lsp::lsp_types::Range>| {
+            range.map_or(false, |range| {
+                range.start <= position && position <= range.end
+            })
+        };
+
+        definitions
+            .get_mut()
+            .iter()
+            .find(|definition| {
+                definition
+                    .file
+                    .as_ref()
+                    .map_or(false, |file| file.path == path)
+                    && (contains(definition.key_range) || contains(definition.range))
+            })
+            .map(|definition| definition.get_namespaced_key())
+    }
+
+    /// Suggests keys that are translated in other files but missing from the translation
+    /// file at `path`, relative to the group that is being edited
+    fn get_missing_key_completions(
+        &self,
+        path: &Path,
+        document: &FullTextDocument,
+        pos: &Pos,
+    ) -> Option<Vec<CompletionItem>> {
+        let language = path
+            .extension()
+            .and_then(OsStr::to_str)
+            .and_then(tree_sitter_helper::get_language_by_extension)?;
+
+        let text = document.text.text();
+        let offset = get_offset_of_position(&document.text, pos);
+
+        // The key being typed starts after the quote or indentation before it
+        let line_start = text[..offset].rfind('\n').map_or(0, |index| index + 1);
+        let key_start = text[line_start..offset]
+            .rfind(|char: char| char.is_whitespace() || "'\"{,".contains(char))
+            .map_or(line_start, |index| line_start + index + 1);
+
+        let config = self.config.lock().unwrap().get_mut().clone();
+
+        // Identifiers are compared after the key filter, so the group path is filtered as well
+        let group_path = tree_sitter_helper::get_cleaned_group_path(
+            &tree_sitter_helper::get_group_path_at_offset(text, language, offset),
+            &config,
+        );
+
+        let plural_separator = config
+            .plural
+            .as_ref()
+            .map(|plural_config| plural_config.separator.clone());
+
+        let mut definitions = self.definitions.lock().unwrap();
+        let definitions = definitions.get_mut();
+
+        let is_in_file = |definition: &Definition| {
+            definition
+                .file
+                .as_ref()
+                .map_or(false, |file| file.path == path)
+        };
+
+        // Plural variants are written with their category, like they are in the other files
+        let get_written_key = |definition: &Definition| match (
+            &definition.plural_category,
+            plural_separator.as_ref(),
+        ) {
+            (Some(category), Some(separator)) => {
+                format!("{}{}{}", definition.get_identifier(), separator, category)
+            }
+            _ => definition.get_identifier().clone(),
+        };
 
-        let new_definitions_result = tree_sitter_helper::parse_translation_structure(
-            file,
-            self.config.lock().unwrap().get_mut(),
-            language.unwrap(),
-            query_source.unwrap(),
-        );
+        let namespace = definitions
+            .iter()
+            .find(|definition| is_in_file(definition))
+            .and_then(|definition| definition.get_namespace().cloned());
 
-        match new_definitions_result {
-            Some(mut new_definitions) => {
-                // Use file regex language for all above definitions
-                let mut extra_data = HashMap::<String, String>::new();
+        let existing_keys = definitions
+            .iter()
+            .filter(|definition| is_in_file(definition))
+            .map(get_written_key)
+            .collect::<Vec<String>>();
 
-                // Use file regex language for all above definitions
-                if let Some(file_name_details_regex) = self
-                    .config
-                    .lock()
-                    .unwrap()
-                    .get_mut()
-                    .file_name
-                    .details
-                    .as_ref()
-                {
-                    if let Some(cap) =
-                        file_name_details_regex.captures(path.file_name().unwrap().to_str().unwrap())
-                    {
-                        for capture_group_name in file_name_details_regex.capture_names().flatten() {
-                            let capture_group_result = cap.name(capture_group_name);
-        
-                            if capture_group_result.is_some() {
-                                extra_data.insert(
-                                    capture_group_name.to_string(),
-                                    capture_group_result.unwrap().as_str().to_string(),
-                                );
-                            }
-                        }
+        let start = document
+            .text
+            .offset_to_pos(key_start)
+            .and_then(|start| document.text.pos_to_lsp_pos(&start))?;
+        let end = document.text.pos_to_lsp_pos(pos)?;
+
+        Some(
+            definitions
+                .iter()
+                .filter(|definition| {
+                    !is_in_file(definition)
+                        && definition.file.is_some()
+                        && definition.get_namespace() == namespace.as_ref()
+                })
+                .map(|definition| (get_written_key(definition), definition))
+                .filter(|(key, _)| !existing_keys.contains(key))
+                .unique_by(|(key, _)| key.clone())
+                .filter_map(|(key, definition)| {
+                    let label = if group_path.is_empty() {
+                        key
+                    } else {
+                        key.strip_prefix(group_path.as_str())?
+                            .strip_prefix(TRANSLATION_KEY_DIVIDER)?
+                            .to_string()
                     };
-                };
 
-                let translation_file = DefinitionSource {
-                    path: path.to_path_buf(),
-                    extra_data,
-                };
+                    Some(CompletionItem {
+                        label: label.clone(),
+                        kind: Some(CompletionItemKind::TEXT),
+                        detail: Some(definition.get_preview_value()),
+                        text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+                            range: tower_lsp::lsp_types::Range::new(start, end),
+                            new_text: label,
+                        })),
+                        data: Some(serde_json::to_value(definition.get_namespaced_key()).unwrap()),
+                        ..Default::default()
+                    })
+                })
+                .collect(),
+        )
+    }
 
-                for definition in new_definitions.iter_mut() {
-                    definition.file = Some(translation_file.clone());
-                }
+    /// Returns the languages that have translations in the namespace of `key`,
+    /// but do not translate `key` itself
+    fn get_missing_languages(&self, key: &NamespacedKey) -> Vec<String> {
+        let mut definitions = self.definitions.lock().unwrap();
+        let definitions = definitions.get_mut();
+
+        let translated_languages = definitions
+            .iter()
+            .filter(|definition| *definition == key)
+            .filter_map(|definition| definition.get_language())
+            .collect::<Vec<&String>>();
 
-                let mut definitions = self.definitions.lock().unwrap();
-                new_definitions.append(definitions.get_mut());
-                definitions.set(new_definitions);
+        definitions
+            .iter()
+            .filter(|definition| {
+                key.namespace.is_none() || definition.get_namespace() == key.namespace.as_ref()
+            })
+            .filter_map(|definition| definition.get_language())
+            .unique()
+            .filter(|language| !translated_languages.contains(language))
+            .sorted()
+            .cloned()
+            .collect()
+    }
 
-                Ok(())
+    /// Resolves a key as written in `text` at `offset` to the key it refers to,
+    /// taking the namespace configuration into account
+    fn resolve_key(&self, key: &str, text: &str, offset: usize) -> NamespacedKey {
+        match self.config.lock().unwrap().get_mut().namespace.as_ref() {
+            Some(namespace_config) => {
+                namespace_helper::resolve_key(key, text, offset, namespace_config)
             }
-            None => Err(Box::new(InvalidTranslationFileStructure)),
+            None => NamespacedKey::without_namespace(key),
         }
     }
 
     /// Gets details about a single definition
-    fn get_definition_detail_by_key(&self, key: &String) -> Option<String> {
+    fn get_definition_detail_by_key(&self, key: &NamespacedKey) -> Option<String> {
+        let config = self.config.lock().unwrap().get_mut().clone();
+        let has_namespaces = config.namespace.is_some();
+
         if let Ok(ref mut definitions) = self.definitions.try_lock() {
-            let definitions_same_key = definitions
-                .get_mut()
-                .iter()
-                .filter(|definition| *definition == key);
+            let definitions = &*definitions.get_mut();
+            let definitions_same_key = definitions.iter().filter(|definition| *definition == key);
 
             if definitions_same_key.clone().count() == 0 {
                 return None;
@@ -406,6 +1889,10 @@ impl Backend {
                 .clone()
                 .any(|definition| definition.get_flag().is_some());
 
+            let has_plural = definitions_same_key
+                .clone()
+                .any(|definition| definition.plural_category.is_some());
+
             let extra_data_keys: Vec<&String> = definitions_same_key
                 .clone()
                 .flat_map(|definition| {
@@ -422,6 +1909,7 @@ impl Backend {
                 })
                 .flatten()
                 .filter(|key| *key != &"language".to_string())
+                .filter(|key| !has_namespaces || *key != &"namespace".to_string())
                 .unique()
                 .collect();
 
@@ -435,10 +1923,68 @@ impl Backend {
                 table_headers.push(&data_key);
             });
 
+            if has_plural {
+                table_headers.push("plural");
+            }
+
             table_headers.push("translation");
 
-            let body = definitions_same_key
-                .map(|def| {
+            let definitions_same_key_list =
+                definitions_same_key.clone().collect::<Vec<&Definition>>();
+
+            let mut arb_metadata = self.arb_metadata.lock().unwrap();
+            let metadata_max_lengths =
+                max_length_helper::get_metadata_max_lengths(arb_metadata.get_mut());
+
+            // Rows are a definition with its plural category and whether that category is missing
+            let mut rows = definitions_same_key
+                .map(|def| (def, def.plural_category.clone(), false))
+                .collect::<Vec<(&Definition, Option<String>, bool)>>();
+
+            if has_plural {
+                let languages = rows
+                    .iter()
+                    .filter_map(|(def, _, _)| def.get_language())
+                    .unique()
+                    .cloned()
+                    .collect::<Vec<String>>();
+
+                // Add a row for every plural category the language needs but does not define
+                for language in languages.iter() {
+                    let language_definition = rows
+                        .iter()
+                        .find(|(def, _, _)| def.get_language() == Some(language))
+                        .unwrap()
+                        .0;
+
+                    for category in plural_helper::get_plural_categories(language).unwrap_or(&[]) {
+                        let is_defined = rows.iter().any(|(def, row_category, _)| {
+                            def.get_language() == Some(language)
+                                && row_category.as_deref() == Some(*category)
+                        });
+
+                        if !is_defined {
+                            rows.push((language_definition, Some(category.to_string()), true));
+                        }
+                    }
+                }
+
+                // Group the rows per language, ordered by plural category
+                rows.sort_by_key(|(def, category, _)| {
+                    (
+                        languages
+                            .iter()
+                            .position(|language| Some(language) == def.get_language()),
+                        category
+                            .as_deref()
+                            .map_or(PLURAL_CATEGORIES.len(), get_plural_category_order),
+                    )
+                });
+            }
+
+            let body = rows
+                .into_iter()
+                .map(|(def, category, is_missing)| {
                     let mut row_data = Vec::<String>::new();
                     if has_flag || has_language {
                         row_data.push(def.get_flag().unwrap_or("🏴󠁢󠁳󠁢󠁰󠁿".to_string()));
@@ -450,14 +1996,72 @@ impl Backend {
                     }
 
                     extra_data_keys.iter().for_each(|data_key| {
-                        row_data.push(
+                        row_data.push(if is_missing {
+                            "-".to_string()
+                        } else {
                             def.get_full_extra_data(*data_key)
                                 .unwrap_or(&"-".to_string())
-                                .to_string(),
-                        );
+                                .to_string()
+                        });
                     });
 
-                    row_data.push(def.get_printable_value());
+                    if has_plural {
+                        row_data.push(category.unwrap_or_else(|| "-".to_string()));
+                    }
+
+                    row_data.push(if is_missing {
+                        "*missing*".to_string()
+                    } else {
+                        let placeholder_mismatch =
+                            config
+                                .get_reference_language()
+                                .and_then(|reference_language| {
+                                    placeholder_helper::get_placeholder_mismatch(
+                                        def,
+                                        &definitions_same_key_list,
+                                        reference_language,
+                                        &config.placeholders.syntax,
+                                    )
+                                });
+
+                        // ICU messages with plural or select arguments list their options
+                        let value = match config.message_format {
+                            Some(MessageFormat::Icu) => icu_helper::parse_message(&def.value)
+                                .ok()
+                                .filter(|parts| icu_helper::has_select(parts))
+                                .map(|parts| icu_helper::render_message(&parts)),
+                            None => None,
+                        }
+                        .unwrap_or_else(|| def.get_printable_value());
+
+                        let value = match max_length_helper::get_max_length(
+                            def,
+                            &metadata_max_lengths,
+                            &config.diagnostics.max_length,
+                        ) {
+                            Some(max_length) => {
+                                let length = max_length_helper::get_length(&def.value);
+
+                                format!(
+                                    "{} {}`{}/{}`",
+                                    value,
+                                    if length > max_length { "⚠️ " } else { "" },
+                                    length,
+                                    max_length
+                                )
+                            }
+                            None => value,
+                        };
+
+                        match placeholder_mismatch {
+                            Some(mismatch) => format!(
+                                "{} ⚠️ *{}*",
+                                value,
+                                mismatch.to_string().replace("|", "\\|")
+                            ),
+                            None => value,
+                        }
+                    });
 
                     row_data.join("|")
                 })
@@ -493,7 +2097,7 @@ impl Backend {
                 self.fetch_translations(config[0].clone()).await;
                 self.client
                     .log_message(
-                        MessageType::Info,
+                        MessageType::INFO,
                         format!(
                             "Loaded {:} definitions in {:#?}",
                             self.definitions.lock().unwrap().get_mut().len(),
@@ -502,14 +2106,17 @@ impl Backend {
                     )
                     .await;
             }
-            Err(err) => self.client.log_message(MessageType::Error, err).await,
+            Err(err) => self.client.log_message(MessageType::ERROR, err).await,
         }
     }
 }
 
 #[tower_lsp::async_trait]
 impl LanguageServer for Backend {
-    async fn initialize(&self, _: InitializeParams) -> jsonrpc::Result<InitializeResult> {
+    async fn initialize(&self, params: InitializeParams) -> jsonrpc::Result<InitializeResult> {
+        let pull_diagnostics = PullDiagnosticsSupport::from_capabilities(&params.capabilities);
+        self.pull_diagnostics.lock().unwrap().set(pull_diagnostics);
+
         let mut trigger_characters = TRANSLATION_BEGIN_CHARS
             .to_vec()
             .iter()
@@ -522,15 +2129,27 @@ impl LanguageServer for Backend {
             server_info: None,
             capabilities: ServerCapabilities {
                 text_document_sync: Some(TextDocumentSyncCapability::Kind(
-                    TextDocumentSyncKind::Incremental,
+                    TextDocumentSyncKind::INCREMENTAL,
                 )),
                 hover_provider: Some(HoverProviderCapability::Simple(true)),
+                workspace_symbol_provider: Some(OneOf::Left(true)),
+                document_symbol_provider: Some(OneOf::Left(true)),
+                code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+                code_lens_provider: Some(CodeLensOptions {
+                    resolve_provider: Some(true),
+                }),
                 completion_provider: Some(CompletionOptions {
                     resolve_provider: Some(true),
                     trigger_characters: Some(trigger_characters),
                     work_done_progress_options: Default::default(),
                     all_commit_characters: None,
+                    completion_item: None,
                 }),
+                diagnostic_provider: if pull_diagnostics.enabled {
+                    Some(pull_diagnostics::get_diagnostic_provider())
+                } else {
+                    None
+                },
                 workspace: Some(WorkspaceServerCapabilities {
                     workspace_folders: Some(WorkspaceFoldersServerCapabilities {
                         supported: Some(true),
@@ -574,36 +2193,123 @@ impl LanguageServer for Backend {
             .unwrap()
             .get_mut()
             .push(FullTextDocument::new(
-                params.text_document.uri,
+                params.text_document.uri.clone(),
                 params.text_document.language_id,
                 params.text_document.version.into(),
-                params.text_document.text,
+                params.text_document.text.clone(),
             ));
+
+        let uri = params.text_document.uri;
+        if self.get_translation_file_path(&uri).is_none() {
+            let previous_keys = self.get_used_keys(&uri);
+            self.index_usages(&uri, &IndexedText::new(params.text_document.text));
+
+            // Only new usages can make keys of the translation files (un)used
+            let translation_files = self.get_used_keys(&uri) != previous_keys;
+
+            self.publish_pending_diagnostics(PendingDiagnostics {
+                documents: vec![uri],
+                translation_files,
+                ..Default::default()
+            })
+            .await;
+        }
     }
 
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
-        if let Ok(ref mut definitions) = self.documents.try_lock() {
-            let documents = definitions.get_mut();
+        let document = match self.documents.try_lock() {
+            Ok(ref mut documents) => {
+                let document = documents
+                    .get_mut()
+                    .iter_mut()
+                    .find(|doc| doc.uri == params.text_document.uri)
+                    .unwrap();
 
-            let document = documents
-                .iter_mut()
-                .find(|doc| doc.uri == params.text_document.uri)
-                .unwrap();
+                for content_change in params.content_changes {
+                    let change = document.text.lsp_change_to_change(content_change).unwrap();
 
-            for content_change in params.content_changes {
-                let change = document.text.lsp_change_to_change(content_change).unwrap();
+                    document.text = IndexedText::new(apply_change(&document.text, change));
+                }
+                document.version = params.text_document.version.into();
+
+                document.clone()
+            }
+            Err(_) => return,
+        };
 
-                document.text = IndexedText::new(apply_change(&document.text, change));
+        // The definitions follow the unsaved content of translation files
+        if let Some(path) = self.get_translation_file_path(&document.uri) {
+            let folders = self.workspace_folders.lock().unwrap().get_mut().clone();
+
+            let result = self
+                .reload_translation(&path, document.text.text().to_string(), &folders)
+                .map_err(|err| format!("Could not read translation file {:?}: {:?}", path, err));
+
+            if let Err(message) = result {
+                self.client.log_message(MessageType::ERROR, message).await;
             }
+
+            self.schedule_diagnostics(document.uri, true);
+            return;
         }
+
+        let previous_keys = self.get_used_keys(&document.uri);
+        self.index_usages(&document.uri, &document.text);
+
+        // Only changed usages can make keys of the translation files (un)used
+        let translation_files = self.get_used_keys(&document.uri) != previous_keys;
+
+        self.schedule_diagnostics(document.uri, translation_files);
     }
 
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri = params.text_document.uri;
+
         self.documents
             .lock()
             .unwrap()
             .get_mut()
-            .retain(|document| document.uri != params.text_document.uri)
+            .retain(|document| document.uri != uri);
+
+        // Unsaved changes of the closed document are discarded, so the files are read again
+        if let Some(path) = self.get_translation_file_path(&uri) {
+            let folders = self.workspace_folders.lock().unwrap().get_mut().clone();
+
+            let result = fs::read_to_string(&path)
+                .map_err(|err| err.into())
+                .and_then(|text| self.reload_translation(&path, text, &folders))
+                .map_err(|err| format!("Could not read translation file {:?}: {:?}", path, err));
+
+            if let Err(message) = result {
+                self.client.log_message(MessageType::ERROR, message).await;
+            }
+
+            self.schedule_diagnostics(uri, true);
+            return;
+        }
+
+        let previous_keys = self.get_used_keys(&uri);
+
+        let source_text = uri
+            .to_file_path()
+            .ok()
+            .filter(|path| self.source_files.lock().unwrap().get_mut().contains(path))
+            .and_then(|path| fs::read_to_string(path).ok());
+
+        match source_text {
+            Some(text) => self.index_usages(&uri, &IndexedText::new(text)),
+            // Documents outside of the source files no longer use any keys
+            None => self
+                .usages
+                .lock()
+                .unwrap()
+                .get_mut()
+                .retain(|usage| usage.location.uri != uri),
+        }
+
+        if self.get_used_keys(&uri) != previous_keys {
+            self.schedule_diagnostics(uri, true);
+        }
     }
 
     async fn completion(
@@ -625,38 +2331,229 @@ impl LanguageServer for Backend {
             .lsp_pos_to_pos(&params.text_document_position.position)
             .unwrap();
 
+        if let Some(path) = self.get_translation_file_path(&document.uri) {
+            return Ok(self
+                .get_missing_key_completions(&path, &document, &pos)
+                .map(CompletionResponse::Array));
+        }
+
+        if let Some(items) = self.get_interpolation_completions(&document, &pos) {
+            return Ok(Some(CompletionResponse::Array(items)));
+        }
+
         let range_result = get_editing_range(&document.text, &pos);
         if range_result.is_none() {
             return Ok(None);
         };
 
+        let namespace_config = self.config.lock().unwrap().get_mut().namespace.clone();
+        let completion_config = self.config.lock().unwrap().get_mut().completion.clone();
+        let scope = namespace_helper::find_translation_scope(
+            document.text.text(),
+            get_offset_of_position(&document.text, &pos),
+        );
+
         if let Ok(ref mut definitions) = self.definitions.try_lock() {
             let definitions = definitions.get_mut();
             let range = range_result.unwrap();
 
-            Ok(Some(CompletionResponse::Array(
-                definitions
-                    .iter()
-                    .unique_by(|definition| definition.get_identifier())
-                    .map(|definition| CompletionItem {
-                        label: definition.get_identifier().to_string(),
-                        kind: Some(CompletionItemKind::Text),
-                        detail: None,
-                        text_edit: Some(CompletionTextEdit::Edit(TextEdit {
-                            range: tower_lsp::lsp_types::Range::new(
-                                document.text.pos_to_lsp_pos(&range.start).unwrap(),
-                                document.text.pos_to_lsp_pos(&range.end).unwrap(),
-                            ),
-                            new_text: definition
-                                .cleaned_key
-                                .as_ref()
-                                .unwrap_or(&definition.key)
-                                .clone(),
-                        })),
-                        ..Default::default()
-                    })
-                    .collect(),
-            )))
+            // The key as it should be written at this position, with the definition it refers to
+            let keys = definitions
+                .iter()
+                .unique_by(|definition| (definition.get_namespace(), definition.get_identifier()))
+                .map(|definition| match &namespace_config {
+                    Some(namespace_config) => (
+                        namespace_helper::get_key_for_scope(
+                            &definition.get_namespaced_key(),
+                            &scope,
+                            namespace_config,
+                        ),
+                        definition,
+                    ),
+                    None => (definition.get_identifier().to_string(), definition),
+                })
+                .collect::<Vec<(String, &Definition)>>();
+
+            // The preview of every key, looked up once so large projects stay fast
+            let mut previews = HashMap::<(Option<&String>, &String), &Definition>::new();
+            for definition in definitions.iter() {
+                let is_preferred = completion_config.preferred_language.is_some()
+                    && definition.get_language() == completion_config.preferred_language.as_ref();
+
+                let preview = previews
+                    .entry((definition.get_namespace(), definition.get_identifier()))
+                    .or_insert(definition);
+
+                if is_preferred
+                    && preview.get_language() != completion_config.preferred_language.as_ref()
+                {
+                    *preview = definition;
+                }
+            }
+
+            let get_preview = |definition: &Definition| {
+                previews
+                    .get(&(definition.get_namespace(), definition.get_identifier()))
+                    .copied()
+            };
+            // The full value as detail and a single line preview next to the label
+            let get_detail = |definition: &Definition| {
+                get_preview(definition).map(|preview| preview.value.clone())
+            };
+            let get_label_details = |definition: &Definition| {
+                get_preview(definition).map(|preview| CompletionItemLabelDetails {
+                    detail: None,
+                    description: Some(preview.get_preview_value()),
+                })
+            };
+
+            let typed_key = document.text.substr(range.start..pos).unwrap_or("");
+
+            if completion_config.hierarchical {
+                // Only the segment after the last divider is replaced
+                let segment_start = document
+                    .text
+                    .offset_to_pos(
+                        get_offset_of_position(&document.text, &range.start)
+                            + get_parent_key_length(typed_key),
+                    )
+                    .unwrap();
+
+                return Ok(Some(CompletionResponse::Array(
+                    keys.iter()
+                        .filter_map(|(key, definition)| {
+                            get_next_key_segment(key, typed_key)
+                                .map(|(segment, is_group)| (segment, is_group, definition))
+                        })
+                        .unique_by(|(segment, is_group, _)| (segment.clone(), *is_group))
+                        .map(|(segment, is_group, definition)| CompletionItem {
+                            label: segment.clone(),
+                            detail: if is_group {
+                                None
+                            } else {
+                                get_detail(definition)
+                            },
+                            label_details: if is_group {
+                                None
+                            } else {
+                                get_label_details(definition)
+                            },
+                            kind: Some(if is_group {
+                                CompletionItemKind::MODULE
+                            } else {
+                                CompletionItemKind::TEXT
+                            }),
+                            text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+                                range: tower_lsp::lsp_types::Range::new(
+                                    document.text.pos_to_lsp_pos(&segment_start).unwrap(),
+                                    document.text.pos_to_lsp_pos(&range.end).unwrap(),
+                                ),
+                                new_text: if is_group {
+                                    format!("{}{}", segment, TRANSLATION_KEY_DIVIDER)
+                                } else {
+                                    segment
+                                },
+                            })),
+                            // Selecting a group continues with the completion of the next level
+                            command: if is_group {
+                                Some(Command {
+                                    title: "Suggest next segment".to_string(),
+                                    command: "editor.action.triggerSuggest".to_string(),
+                                    arguments: None,
+                                })
+                            } else {
+                                None
+                            },
+                            data: if is_group {
+                                None
+                            } else {
+                                Some(serde_json::to_value(definition.get_namespaced_key()).unwrap())
+                            },
+                            ..Default::default()
+                        })
+                        .collect(),
+                )));
+            }
+
+            let create_item = |label: String, definition: &Definition| CompletionItem {
+                label: label.clone(),
+                kind: Some(CompletionItemKind::TEXT),
+                detail: get_detail(definition),
+                label_details: get_label_details(definition),
+                text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+                    range: tower_lsp::lsp_types::Range::new(
+                        document.text.pos_to_lsp_pos(&range.start).unwrap(),
+                        document.text.pos_to_lsp_pos(&range.end).unwrap(),
+                    ),
+                    new_text: label,
+                })),
+                // Items carry their namespaced key when the label is not the full key
+                data: namespace_config
+                    .as_ref()
+                    .map(|_| serde_json::to_value(definition.get_namespaced_key()).unwrap()),
+                ..Default::default()
+            };
+
+            if typed_key.is_empty() || completion_config.value_search == ValueSearch::Off {
+                return Ok(Some(CompletionResponse::Array(
+                    keys.into_iter()
+                        .map(|(label, definition)| create_item(label, definition))
+                        .collect(),
+                )));
+            }
+
+            // The values of every key that can be searched, collected once
+            let mut values = HashMap::<(Option<&String>, &String), Vec<&String>>::new();
+            for definition in definitions.iter() {
+                let is_searchable = match completion_config.value_search {
+                    ValueSearch::PreferredLanguage => {
+                        completion_config.preferred_language.is_none()
+                            || definition.get_language()
+                                == completion_config.preferred_language.as_ref()
+                    }
+                    _ => true,
+                };
+
+                if is_searchable {
+                    values
+                        .entry((definition.get_namespace(), definition.get_identifier()))
+                        .or_default()
+                        .push(&definition.value);
+                }
+            }
+
+            let items = keys
+                .into_iter()
+                .filter_map(|(label, definition)| {
+                    let key_score = get_match_score(&label, typed_key);
+                    let value_score = values
+                        .get(&(definition.get_namespace(), definition.get_identifier()))
+                        .and_then(|values| {
+                            values
+                                .iter()
+                                .filter_map(|value| get_value_match_score(value, typed_key))
+                                .min()
+                        });
+
+                    let score = key_score.into_iter().chain(value_score).min()?;
+
+                    let mut item = create_item(label, definition);
+                    item.sort_text = Some(format!("{:010}", score));
+
+                    // Keys that only match on their value would be hidden by the client otherwise
+                    if key_score.is_none() {
+                        item.filter_text = Some(typed_key.to_string());
+                    }
+
+                    Some(item)
+                })
+                .collect();
+
+            // The client has to ask again as values do not match the way it filters
+            Ok(Some(CompletionResponse::List(CompletionList {
+                is_incomplete: true,
+                items,
+            })))
         } else {
             Err(Error::internal_error())
         }
@@ -664,7 +2561,15 @@ impl LanguageServer for Backend {
 
     async fn completion_resolve(&self, params: CompletionItem) -> jsonrpc::Result<CompletionItem> {
         let mut item = params;
-        if let Some(detail) = self.get_definition_detail_by_key(&item.label) {
+
+        // Items carry their namespaced key as data when the label is not the full key
+        let key = item
+            .data
+            .clone()
+            .and_then(|data| serde_json::from_value::<NamespacedKey>(data).ok())
+            .unwrap_or_else(|| NamespacedKey::without_namespace(&item.label));
+
+        if let Some(detail) = self.get_definition_detail_by_key(&key) {
             item.documentation = Some(Documentation::MarkupContent(MarkupContent {
                 kind: MarkupKind::Markdown,
                 value: detail,
@@ -690,9 +2595,29 @@ impl LanguageServer for Backend {
             .lsp_pos_to_pos(&params.text_document_position_params.position)
             .unwrap();
 
+        // Translation files show the other languages of the hovered key
+        if let Some(path) = self.get_translation_file_path(&document.uri) {
+            return Ok(self
+                .get_translation_file_definition_at(
+                    &path,
+                    params.text_document_position_params.position,
+                )
+                .and_then(|key| self.get_definition_detail_by_key(&key))
+                .map(|contents| Hover {
+                    contents: HoverContents::Scalar(MarkedString::String(contents)),
+                    range: None,
+                }));
+        }
+
         match find_translation_key_by_position(&document.text, &pos) {
             Some(translation_key) => {
-                match self.get_definition_detail_by_key(&translation_key.as_str().to_string()) {
+                let key = self.resolve_key(
+                    translation_key.as_str(),
+                    document.text.text(),
+                    translation_key.start(),
+                );
+
+                match self.get_definition_detail_by_key(&key) {
                     Some(contents) => {
                         let key_range = document
                             .text
@@ -715,18 +2640,261 @@ impl LanguageServer for Backend {
             None => Ok(None),
         }
     }
+
+    async fn code_action(
+        &self,
+        params: CodeActionParams,
+    ) -> jsonrpc::Result<Option<CodeActionResponse>> {
+        let is_code = |diagnostic: &Diagnostic, code: &str| {
+            diagnostic.code == Some(NumberOrString::String(code.to_string()))
+        };
+
+        let mut actions = params
+            .context
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| is_code(diagnostic, diagnostic_helper::UNUSED_KEY_CODE))
+            .filter_map(|diagnostic| {
+                let key = serde_json::from_value::<NamespacedKey>(diagnostic.data.clone()?).ok()?;
+
+                Some(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: format!("Remove `{}` from all translation files", key.key),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    edit: Some(self.get_remove_key_edit(&key)),
+                    ..Default::default()
+                }))
+            })
+            .collect::<Vec<CodeActionOrCommand>>();
+
+        actions.extend(
+            params
+                .context
+                .diagnostics
+                .iter()
+                .filter(|diagnostic| is_code(diagnostic, diagnostic_helper::KEY_NAMING_CODE))
+                .filter_map(|diagnostic| {
+                    let key =
+                        serde_json::from_value::<NamespacedKey>(diagnostic.data.clone()?).ok()?;
+                    let (renamed_key, edit) = self.get_rename_key_edit(&key)?;
+
+                    Some(CodeActionOrCommand::CodeAction(CodeAction {
+                        title: format!("Rename `{}` to `{}` in all files", key.key, renamed_key),
+                        kind: Some(CodeActionKind::QUICKFIX),
+                        diagnostics: Some(vec![diagnostic.clone()]),
+                        edit: Some(edit),
+                        ..Default::default()
+                    }))
+                }),
+        );
+
+        Ok(Some(actions))
+    }
+
+    async fn code_lens(&self, params: CodeLensParams) -> jsonrpc::Result<Option<Vec<CodeLens>>> {
+        let path = match self.get_translation_file_path(&params.text_document.uri) {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+
+        let mut definitions = self.definitions.lock().unwrap();
+
+        // The counts are calculated when the lens is resolved, as that is only done for visible keys
+        Ok(Some(
+            definitions
+                .get_mut()
+                .iter()
+                .filter(|definition| {
+                    definition
+                        .file
+                        .as_ref()
+                        .map_or(false, |file| file.path == path)
+                })
+                // Keys copied by a YAML alias would all be shown on the alias
+                .filter(|definition| definition.key_range.is_some())
+                .filter_map(|definition| {
+                    Some(CodeLens {
+                        range: definition.range?,
+                        command: None,
+                        data: Some(
+                            serde_json::to_value(CodeLensData {
+                                uri: params.text_document.uri.clone(),
+                                key: definition.get_namespaced_key(),
+                            })
+                            .unwrap(),
+                        ),
+                    })
+                })
+                .collect(),
+        ))
+    }
+
+    async fn code_lens_resolve(&self, params: CodeLens) -> jsonrpc::Result<CodeLens> {
+        let mut code_lens = params;
+
+        let data = match code_lens
+            .data
+            .clone()
+            .and_then(|data| serde_json::from_value::<CodeLensData>(data).ok())
+        {
+            Some(data) => data,
+            None => return Ok(code_lens),
+        };
+
+        let locations = self
+            .usages
+            .lock()
+            .unwrap()
+            .get_mut()
+            .iter()
+            .filter(|usage| {
+                usage.key.key == data.key.key
+                    && (usage.key.namespace.is_none()
+                        || data.key.namespace.is_none()
+                        || usage.key.namespace == data.key.namespace)
+            })
+            .map(|usage| usage.location.clone())
+            .collect::<Vec<Location>>();
+
+        let mut title = match locations.len() {
+            1 => "used 1 time".to_string(),
+            count => format!("used {} times", count),
+        };
+
+        let missing_languages = self.get_missing_languages(&data.key);
+        if !missing_languages.is_empty() {
+            title = format!("{} · missing in {}", title, missing_languages.join(", "));
+        }
+
+        code_lens.command = Some(Command {
+            title,
+            command: "editor.action.showReferences".to_string(),
+            arguments: Some(vec![
+                json!(data.uri),
+                json!(code_lens.range.start),
+                json!(locations),
+            ]),
+        });
+
+        Ok(code_lens)
+    }
+
+    async fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> jsonrpc::Result<Option<DocumentSymbolResponse>> {
+        let path = match self.get_translation_file_path(&params.text_document.uri) {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+
+        let language = match path
+            .extension()
+            .and_then(OsStr::to_str)
+            .and_then(tree_sitter_helper::get_language_by_extension)
+        {
+            Some(language) => language,
+            None => return Ok(None),
+        };
+
+        // Prefer the open document as it may contain unsaved changes
+        let text = match self.get_file_text(&path) {
+            Some(text) => text,
+            None => return Ok(None),
+        };
+
+        Ok(Some(DocumentSymbolResponse::Nested(
+            tree_sitter_helper::get_document_symbols(&text, language),
+        )))
+    }
+
+    #[allow(deprecated)]
+    async fn symbol(
+        &self,
+        params: WorkspaceSymbolParams,
+    ) -> jsonrpc::Result<Option<Vec<SymbolInformation>>> {
+        let separator = self
+            .config
+            .lock()
+            .unwrap()
+            .get_mut()
+            .namespace
+            .as_ref()
+            .map(|namespace_config| namespace_config.separator.clone());
+
+        let mut definitions = self.definitions.lock().unwrap();
+
+        // Keys are searched like completion items, so matching keys rank above matching values
+        let mut symbols = definitions
+            .get_mut()
+            .iter()
+            .filter_map(|definition| {
+                let name = match (definition.get_namespace(), separator.as_ref()) {
+                    (Some(namespace), Some(separator)) => {
+                        format!("{}{}{}", namespace, separator, definition.get_identifier())
+                    }
+                    _ => definition.get_identifier().clone(),
+                };
+
+                let score = if params.query.is_empty() {
+                    0
+                } else {
+                    get_match_score(&name, &params.query)
+                        .or_else(|| get_value_match_score(&definition.value, &params.query))?
+                };
+
+                Some((
+                    score,
+                    SymbolInformation {
+                        name,
+                        kind: SymbolKind::STRING,
+                        tags: None,
+                        deprecated: None,
+                        location: definition.get_location()?,
+                        container_name: definition.get_language().cloned(),
+                    },
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        symbols.sort_by(|(score, symbol), (other_score, other_symbol)| {
+            score
+                .cmp(other_score)
+                .then_with(|| symbol.name.cmp(&other_symbol.name))
+        });
+
+        Ok(Some(
+            symbols.into_iter().map(|(_, symbol)| symbol).collect(),
+        ))
+    }
+
+    async fn diagnostic(
+        &self,
+        params: DocumentDiagnosticParams,
+    ) -> jsonrpc::Result<DocumentDiagnosticReportResult> {
+        Ok(pull_diagnostics::get_document_report(self, params))
+    }
+
+    async fn workspace_diagnostic(
+        &self,
+        params: WorkspaceDiagnosticParams,
+    ) -> jsonrpc::Result<WorkspaceDiagnosticReportResult> {
+        Ok(pull_diagnostics::get_workspace_report(self, params))
+    }
 }
 
 #[tokio::main]
 async fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    if args.first().map(String::as_str) == Some(check::COMMAND) {
+        std::process::exit(check::run(&args[1..]));
+    }
+
     let stdin = tokio::io::stdin();
     let stdout = tokio::io::stdout();
 
-    let (service, messages) = LspService::new(Backend::new);
-    Server::new(stdin, stdout)
-        .interleave(messages)
-        .serve(service)
-        .await;
+    let (service, socket) = LspService::new(Backend::new);
+    Server::new(stdin, stdout, socket).serve(service).await;
 }
 
 #[derive(Debug, Clone)]
@@ -735,6 +2903,24 @@ struct DefinitionSource {
     extra_data: HashMap<String, String>,
 }
 
+/// Identifies the key of a code lens until it is resolved
+#[derive(Serialize, Deserialize, Debug)]
+struct CodeLensData {
+    uri: Url,
+    key: NamespacedKey,
+}
+
+/// A place in the source files where a translation key is used
+#[derive(Debug, Clone)]
+struct Usage {
+    key: NamespacedKey,
+    location: Location,
+}
+
+/// Longer values are cut off in previews, as completion menus are only one line high
+static PREVIEW_MAX_LENGTH: usize = 60;
+static PREVIEW_ELLIPSIS: &str = "…";
+
 #[derive(Default, Debug)]
 pub struct Definition {
     key: String,
@@ -742,6 +2928,15 @@ pub struct Definition {
     file: Option<DefinitionSource>,
     value: String,
     extra_data: HashMap<String, String>,
+    /// The plural category of `item_one` like keys, which are grouped under `item`
+    plural_category: Option<String>,
+    /// Where the value is written in the translation file
+    range: Option<tower_lsp::lsp_types::Range>,
+    /// Where the key is written in the translation file,
+    /// `None` for keys that only exist through a YAML alias
+    key_range: Option<tower_lsp::lsp_types::Range>,
+    /// The key that a YAML alias or merge key copied this definition from
+    merged_from: Option<String>,
 }
 
 impl PartialEq for Definition {
@@ -762,12 +2957,31 @@ impl PartialEq<String> for Definition {
     }
 }
 
+impl PartialEq<NamespacedKey> for Definition {
+    fn eq(&self, other: &NamespacedKey) -> bool {
+        self.get_identifier() == &other.key
+            && (other.namespace.is_none() || self.get_namespace() == other.namespace.as_ref())
+    }
+}
+
 impl Definition {
     /// Returns the `cleaned_key` or the `key` if it does not exist.
     fn get_identifier(&self) -> &String {
         self.cleaned_key.as_ref().unwrap_or(&self.key)
     }
 
+    /// Returns the namespace of the translation file, as derived by the namespace configuration
+    fn get_namespace(&self) -> Option<&String> {
+        self.get_full_extra_data("namespace")
+    }
+
+    fn get_namespaced_key(&self) -> NamespacedKey {
+        NamespacedKey {
+            namespace: self.get_namespace().cloned(),
+            key: self.get_identifier().clone(),
+        }
+    }
+
     fn get_full_extra_data(&self, key: &str) -> Option<&String> {
         self.extra_data
             .get(key)
@@ -807,6 +3021,33 @@ impl Definition {
         None
     }
 
+    /// Returns the value on a single line and shortened to `PREVIEW_MAX_LENGTH` characters,
+    /// for previews in places like completion items
+    fn get_preview_value(&self) -> String {
+        let value = self.value.split_whitespace().join(" ");
+
+        if value.chars().count() <= PREVIEW_MAX_LENGTH {
+            return value;
+        }
+
+        let mut preview = value
+            .chars()
+            .take(PREVIEW_MAX_LENGTH - PREVIEW_ELLIPSIS.chars().count())
+            .collect::<String>()
+            .trim_end()
+            .to_string();
+        preview.push_str(PREVIEW_ELLIPSIS);
+        preview
+    }
+
+    /// Returns the location of the value in its translation file
+    fn get_location(&self) -> Option<Location> {
+        Some(Location::new(
+            Url::from_file_path(&self.file.as_ref()?.path).ok()?,
+            self.range?,
+        ))
+    }
+
     fn get_printable_value(&self) -> String {
         /* let newline_regex = Regex::new("\\n").unwrap();
         newline_regex.replace_all(&self.value, "<br />"); */
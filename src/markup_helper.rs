@@ -0,0 +1,171 @@
+use std::fmt;
+
+use itertools::Itertools;
+use regex::Regex;
+
+use crate::Definition;
+
+lazy_static! {
+    // Matches `<b>`, `</b>`, `<br/>`, `<a href="...">` and the indexed tags of `<Trans>`, like `<0>`
+    static ref TAG_REGEX: Regex =
+        Regex::new(r#"<(?P<close>/)?(?P<name>[A-Za-z][\w.:-]*|\d+)(?:\s+[^<>]*?)?\s*(?P<self_closing>/)?>"#)
+            .unwrap();
+}
+
+/// HTML elements that never have a closing tag
+static VOID_ELEMENTS: &[&str] = &["br", "hr", "img", "input", "wbr"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TagKind {
+    Open,
+    Close,
+    SelfClosing,
+}
+
+/// A tag in a translation, attributes are ignored
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Tag {
+    pub name: String,
+    pub kind: TagKind,
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            TagKind::Open => write!(f, "<{}>", self.name),
+            TagKind::Close => write!(f, "</{}>", self.name),
+            TagKind::SelfClosing => write!(f, "<{}/>", self.name),
+        }
+    }
+}
+
+/// Returns the tags of `value` in the order they are written
+pub fn get_tags(value: &str) -> Vec<Tag> {
+    TAG_REGEX
+        .captures_iter(value)
+        .map(|cap| {
+            let name = cap.name("name").unwrap().as_str().to_string();
+
+            let kind = if cap.name("close").is_some() {
+                TagKind::Close
+            } else if cap.name("self_closing").is_some()
+                || VOID_ELEMENTS.contains(&name.to_lowercase().as_str())
+            {
+                TagKind::SelfClosing
+            } else {
+                TagKind::Open
+            };
+
+            Tag { name, kind }
+        })
+        .collect()
+}
+
+/// Returns a message for every tag that is closed without being opened or never closed
+pub fn get_unbalanced_tags(tags: &[Tag]) -> Vec<String> {
+    let mut problems = vec![];
+    let mut open_tags = Vec::<&Tag>::new();
+
+    for tag in tags {
+        match tag.kind {
+            TagKind::Open => open_tags.push(tag),
+            TagKind::Close => match open_tags.iter().rposition(|open| open.name == tag.name) {
+                Some(index) => {
+                    // Tags opened after the matching tag are not closed before it
+                    for open in open_tags.drain(index..).skip(1) {
+                        problems.push(format!("`{}` is not closed", open));
+                    }
+                }
+                None => problems.push(format!("`{}` has no opening tag", tag)),
+            },
+            TagKind::SelfClosing => {}
+        }
+    }
+
+    problems.extend(
+        open_tags
+            .iter()
+            .map(|open| format!("`{}` is not closed", open)),
+    );
+
+    problems
+}
+
+/// The tags of a translation that differ from the reference language
+#[derive(Debug, PartialEq)]
+pub struct TagMismatch {
+    pub missing: Vec<Tag>,
+    pub unexpected: Vec<Tag>,
+}
+
+impl fmt::Display for TagMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let format_tags = |tags: &Vec<Tag>| tags.iter().map(|tag| format!("`{}`", tag)).join(", ");
+
+        let mut parts = vec![];
+        if !self.missing.is_empty() {
+            parts.push(format!("missing {}", format_tags(&self.missing)));
+        }
+        if !self.unexpected.is_empty() {
+            parts.push(format!("unexpected {}", format_tags(&self.unexpected)));
+        }
+
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// Compares the tags of `definition` with the translation in `reference_language`.
+///
+/// Plural variants are compared with the same variant of the reference language,
+/// or with its `other` variant when the reference language does not have that variant.
+pub fn get_tag_mismatch(
+    definition: &Definition,
+    definitions_same_key: &[&Definition],
+    reference_language: &str,
+) -> Option<TagMismatch> {
+    if definition.get_language().map(|language| language.as_str()) == Some(reference_language) {
+        return None;
+    }
+
+    let reference_definitions = definitions_same_key
+        .iter()
+        .filter(|other| {
+            other.get_language().map(|language| language.as_str()) == Some(reference_language)
+        })
+        .collect::<Vec<_>>();
+
+    let reference_definition = reference_definitions
+        .iter()
+        .find(|other| other.plural_category == definition.plural_category)
+        .or_else(|| {
+            reference_definitions
+                .iter()
+                .find(|other| other.plural_category.as_deref() == Some("other"))
+        })
+        .or_else(|| reference_definitions.first())?;
+
+    let mut unexpected = get_tags(&definition.value);
+    let mut missing = vec![];
+
+    for tag in get_tags(&reference_definition.value) {
+        match unexpected.iter().position(|other| *other == tag) {
+            Some(index) => {
+                unexpected.remove(index);
+            }
+            None => missing.push(tag),
+        }
+    }
+
+    if missing.is_empty() && unexpected.is_empty() {
+        return None;
+    }
+
+    Some(TagMismatch {
+        missing,
+        unexpected,
+    })
+}
+
+#[path = "./tests/markup_helper.rs"]
+#[cfg(test)]
+mod test;
@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+use crate::{Definition, MaxLengthRule};
+
+/// The ARB metadata of `key` is written as `"@key": { "maxLength": 24 }`,
+/// usually only in the template file but it applies to every language
+static ARB_METADATA_PREFIX: &str = "@";
+static ARB_MAX_LENGTH_SUFFIX: &str = ".maxLength";
+
+/// Maximum lengths from the metadata of the translation files, by namespace and key
+pub type MetadataMaxLengths<'a> = HashMap<(Option<&'a String>, &'a str), usize>;
+
+/// Returns the length of a translation as it is shown to the user
+pub fn get_length(value: &str) -> usize {
+    value.chars().count()
+}
+
+/// Returns whether `key` of an ARB file is metadata, like `@@locale` or `@key.maxLength`,
+/// instead of a translation
+pub fn is_arb_metadata_key(key: &str) -> bool {
+    key.starts_with(ARB_METADATA_PREFIX)
+}
+
+/// Collects the `@key.maxLength` metadata of ARB files
+pub fn get_metadata_max_lengths(metadata: &[Definition]) -> MetadataMaxLengths<'_> {
+    metadata
+        .iter()
+        .filter_map(|definition| {
+            let key = definition
+                .key
+                .strip_prefix(ARB_METADATA_PREFIX)?
+                .strip_suffix(ARB_MAX_LENGTH_SUFFIX)?;
+
+            Some((
+                (definition.get_namespace(), key),
+                definition.value.trim().parse::<usize>().ok()?,
+            ))
+        })
+        .collect()
+}
+
+/// Returns the maximum length of `definition`, which is the strictest of the rules
+/// in the config that match its key and language and the metadata of its key
+pub fn get_max_length(
+    definition: &Definition,
+    metadata_max_lengths: &MetadataMaxLengths,
+    rules: &[MaxLengthRule],
+) -> Option<usize> {
+    let identifier = definition.get_identifier();
+    let language = definition.get_language();
+
+    let metadata_max_length = metadata_max_lengths
+        .get(&(definition.get_namespace(), definition.key.as_str()))
+        .copied();
+
+    rules
+        .iter()
+        .filter(|rule| rule.key.is_match(identifier))
+        .filter(|rule| {
+            rule.language
+                .as_ref()
+                .map_or(true, |rule_language| Some(rule_language) == language)
+        })
+        .map(|rule| rule.max)
+        .chain(metadata_max_length)
+        .min()
+}
+
+#[path = "./tests/max_length_helper.rs"]
+#[cfg(test)]
+mod test;
@@ -0,0 +1,123 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::string_helper::TRANSLATION_KEY_DIVIDER;
+use crate::NamespaceConfig;
+
+lazy_static! {
+    // Matches `useTranslation('ns')`, `useTranslation(['ns', ...])` and
+    // `useTranslation('ns', { keyPrefix: 'prefix' })`
+    static ref USE_TRANSLATION_REGEX: Regex = Regex::new(
+        r#"useTranslation\(\s*(?:\[\s*)?(?:['"`](?P<namespace>[^'"`]*)['"`])?(?:[^)]*?keyPrefix\s*:\s*['"`](?P<key_prefix>[^'"`]*)['"`])?[^)]*\)"#
+    )
+    .unwrap();
+}
+
+/// A translation key split into the namespace and the key within that namespace
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NamespacedKey {
+    pub namespace: Option<String>,
+    pub key: String,
+}
+
+impl NamespacedKey {
+    /// Creates a key without a namespace, used when namespaces are not configured
+    pub fn without_namespace(key: &str) -> NamespacedKey {
+        NamespacedKey {
+            namespace: None,
+            key: key.to_string(),
+        }
+    }
+}
+
+/// The namespace and key prefix a source file selected with `useTranslation`
+#[derive(Debug, Default, PartialEq)]
+pub struct TranslationScope {
+    pub namespace: Option<String>,
+    pub key_prefix: Option<String>,
+}
+
+/// Finds the `useTranslation` call that applies to `offset`, which is the closest
+/// call before the offset. Keys before any call use the default scope.
+pub fn find_translation_scope(text: &str, offset: usize) -> TranslationScope {
+    let call = USE_TRANSLATION_REGEX
+        .captures_iter(text)
+        .take_while(|cap| cap.get(0).unwrap().start() <= offset)
+        .last();
+
+    match call {
+        Some(cap) => TranslationScope {
+            namespace: cap
+                .name("namespace")
+                .map(|namespace| namespace.as_str().to_string())
+                .filter(|namespace| !namespace.is_empty()),
+            key_prefix: cap
+                .name("key_prefix")
+                .map(|key_prefix| key_prefix.as_str().to_string())
+                .filter(|key_prefix| !key_prefix.is_empty()),
+        },
+        None => TranslationScope::default(),
+    }
+}
+
+/// Resolves a key as written in source to the namespace and full key it refers to.
+///
+/// Keys with an explicit namespace (`ns:key`) are used as-is, bare keys use the
+/// namespace and key prefix of the surrounding `useTranslation` call or the default namespace.
+pub fn resolve_key(
+    key: &str,
+    text: &str,
+    offset: usize,
+    config: &NamespaceConfig,
+) -> NamespacedKey {
+    if let Some((namespace, key)) = key.split_once(config.separator.as_str()) {
+        return NamespacedKey {
+            namespace: Some(namespace.to_string()),
+            key: key.to_string(),
+        };
+    }
+
+    let scope = find_translation_scope(text, offset);
+
+    NamespacedKey {
+        namespace: scope.namespace.or_else(|| config.default.clone()),
+        key: match scope.key_prefix {
+            Some(key_prefix) => format!("{}{}{}", key_prefix, TRANSLATION_KEY_DIVIDER, key),
+            None => key.to_string(),
+        },
+    }
+}
+
+/// Returns the shortest way to write `key` in source given the `useTranslation`
+/// scope at that position; the inverse of `resolve_key`.
+pub fn get_key_for_scope(
+    key: &NamespacedKey,
+    scope: &TranslationScope,
+    config: &NamespaceConfig,
+) -> String {
+    let scope_namespace = scope.namespace.as_ref().or_else(|| config.default.as_ref());
+
+    if key.namespace.is_none() || key.namespace.as_ref() == scope_namespace {
+        match &scope.key_prefix {
+            Some(key_prefix) => {
+                if let Some(key) = key
+                    .key
+                    .strip_prefix(key_prefix.as_str())
+                    .and_then(|key| key.strip_prefix(TRANSLATION_KEY_DIVIDER))
+                {
+                    return key.to_string();
+                }
+            }
+            None => return key.key.clone(),
+        }
+    }
+
+    match &key.namespace {
+        Some(namespace) => format!("{}{}{}", namespace, config.separator, key.key),
+        None => key.key.clone(),
+    }
+}
+
+#[path = "./tests/namespace_helper.rs"]
+#[cfg(test)]
+mod test;
@@ -0,0 +1,207 @@
+use std::fmt;
+use std::ops::Range;
+
+use itertools::Itertools;
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::Definition;
+
+lazy_static! {
+    // `{{name}}`, `{{- name}}` and `{{name, format}}`
+    static ref I18NEXT_REGEX: Regex =
+        Regex::new(r"\{\{-?\s*([^{}\s,]+)\s*(?:,[^{}]*)?\}\}").unwrap();
+    // `%{name}`
+    static ref RAILS_REGEX: Regex = Regex::new(r"%\{\s*(\w+)\s*\}").unwrap();
+    // `%s`, `%.2f` and `%1$s`, where `%%` is an escaped percent sign
+    static ref PRINTF_REGEX: Regex =
+        Regex::new(r"%%|%(?:(\d+)\$)?[-+ 0#]*\d*(?:\.\d+)?([sdifuxXeEgGcp@])").unwrap();
+    // `{name}` and `{count, plural, ...}`
+    static ref ICU_REGEX: Regex = Regex::new(r"\{\s*(\w+)\s*[,}]").unwrap();
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum PlaceholderSyntax {
+    /// `{{name}}`
+    I18next,
+    /// `%{name}`
+    Rails,
+    /// `%s` and `%1$s`
+    Printf,
+    /// `{name}`
+    Icu,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Placeholder {
+    pub syntax: PlaceholderSyntax,
+    pub name: String,
+}
+
+impl fmt::Display for Placeholder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.syntax {
+            PlaceholderSyntax::I18next => write!(f, "{{{{{}}}}}", self.name),
+            PlaceholderSyntax::Rails => write!(f, "%{{{}}}", self.name),
+            PlaceholderSyntax::Printf => write!(f, "%{}", self.name),
+            PlaceholderSyntax::Icu => write!(f, "{{{}}}", self.name),
+        }
+    }
+}
+
+/// Returns the unique placeholders in a value.
+///
+/// Syntaxes are matched in the order of `PlaceholderSyntax`, so the `{name}` within
+/// `{{name}}` or `%{name}` is not also seen as an ICU placeholder.
+pub fn get_placeholders(value: &str, syntaxes: &[PlaceholderSyntax]) -> Vec<Placeholder> {
+    let mut matched_ranges = Vec::<Range<usize>>::new();
+    let mut placeholders = vec![];
+
+    for syntax in syntaxes.iter().sorted() {
+        let regex = get_regex(syntax);
+
+        let mut printf_index = 0;
+
+        for cap in regex.captures_iter(value) {
+            let range = cap.get(0).unwrap().range();
+            if matched_ranges.iter().any(|matched_range| {
+                matched_range.start < range.end && range.start < matched_range.end
+            }) {
+                continue;
+            }
+
+            let name = match syntax {
+                // Placeholders without a position are numbered in the order they appear
+                PlaceholderSyntax::Printf => match cap.get(2) {
+                    Some(conversion) => {
+                        printf_index += 1;
+
+                        let position = cap.get(1).map_or(printf_index.to_string(), |position| {
+                            position.as_str().to_string()
+                        });
+
+                        format!("{}${}", position, conversion.as_str())
+                    }
+                    None => continue,
+                },
+                _ => cap.get(1).unwrap().as_str().to_string(),
+            };
+
+            matched_ranges.push(range);
+            placeholders.push(Placeholder {
+                syntax: *syntax,
+                name,
+            });
+        }
+    }
+
+    placeholders.into_iter().unique().collect()
+}
+
+/// Returns `value` without its placeholders, leaving only the text that is translated
+pub fn remove_placeholders(value: &str, syntaxes: &[PlaceholderSyntax]) -> String {
+    syntaxes
+        .iter()
+        .sorted()
+        .fold(value.to_string(), |value, syntax| {
+            get_regex(syntax).replace_all(&value, "").to_string()
+        })
+}
+
+fn get_regex(syntax: &PlaceholderSyntax) -> &'static Regex {
+    match syntax {
+        PlaceholderSyntax::I18next => &I18NEXT_REGEX,
+        PlaceholderSyntax::Rails => &RAILS_REGEX,
+        PlaceholderSyntax::Printf => &PRINTF_REGEX,
+        PlaceholderSyntax::Icu => &ICU_REGEX,
+    }
+}
+
+/// The placeholders of a translation that differ from the reference language
+#[derive(Debug, PartialEq)]
+pub struct PlaceholderMismatch {
+    pub missing: Vec<Placeholder>,
+    pub unexpected: Vec<Placeholder>,
+}
+
+impl fmt::Display for PlaceholderMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let format_placeholders = |placeholders: &Vec<Placeholder>| {
+            placeholders
+                .iter()
+                .map(|placeholder| format!("`{}`", placeholder))
+                .join(", ")
+        };
+
+        let mut parts = vec![];
+        if !self.missing.is_empty() {
+            parts.push(format!("missing {}", format_placeholders(&self.missing)));
+        }
+        if !self.unexpected.is_empty() {
+            parts.push(format!(
+                "unexpected {}",
+                format_placeholders(&self.unexpected)
+            ));
+        }
+
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// Compares the placeholders of `definition` with the translation in `reference_language`.
+///
+/// Plural variants are compared with all variants of the reference language together,
+/// as languages like English often leave out the count in `one`.
+pub fn get_placeholder_mismatch(
+    definition: &Definition,
+    definitions_same_key: &[&Definition],
+    reference_language: &str,
+    syntaxes: &[PlaceholderSyntax],
+) -> Option<PlaceholderMismatch> {
+    let language = definition.get_language()?;
+    if language == reference_language {
+        return None;
+    }
+
+    let get_language_placeholders = |language: &str| {
+        definitions_same_key
+            .iter()
+            .filter(|other| other.get_language().map(|other| other.as_str()) == Some(language))
+            .flat_map(|other| get_placeholders(&other.value, syntaxes))
+            .unique()
+            .collect::<Vec<Placeholder>>()
+    };
+
+    let reference_placeholders = get_language_placeholders(reference_language);
+    if !definitions_same_key
+        .iter()
+        .any(|other| other.get_language().map(|other| other.as_str()) == Some(reference_language))
+    {
+        return None;
+    }
+
+    let language_placeholders = get_language_placeholders(language);
+
+    let mismatch = PlaceholderMismatch {
+        missing: reference_placeholders
+            .iter()
+            .filter(|placeholder| !language_placeholders.contains(placeholder))
+            .cloned()
+            .collect(),
+        unexpected: get_placeholders(&definition.value, syntaxes)
+            .into_iter()
+            .filter(|placeholder| !reference_placeholders.contains(placeholder))
+            .collect(),
+    };
+
+    if mismatch.missing.is_empty() && mismatch.unexpected.is_empty() {
+        None
+    } else {
+        Some(mismatch)
+    }
+}
+
+#[path = "./tests/placeholder_helper.rs"]
+#[cfg(test)]
+mod test;
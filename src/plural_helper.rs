@@ -0,0 +1,80 @@
+use crate::PluralConfig;
+
+/// All CLDR plural categories in their canonical order
+pub static PLURAL_CATEGORIES: &[&str] = &["zero", "one", "two", "few", "many", "other"];
+
+/// Cardinal plural categories per language, following the CLDR plural rules
+static LANGUAGE_PLURAL_CATEGORIES: &[(&[&str], &[&str])] = &[
+    (
+        &[
+            "bo", "dz", "id", "ig", "ja", "jv", "km", "ko", "lo", "ms", "my", "sah", "th", "to",
+            "vi", "wo", "yo", "zh",
+        ],
+        &["other"],
+    ),
+    (
+        &[
+            "af", "az", "bg", "da", "de", "el", "en", "et", "eu", "fi", "fy", "gl", "hu", "hy",
+            "is", "ka", "kk", "ky", "lb", "ml", "mn", "nb", "nl", "nn", "no", "sq", "sv", "sw",
+            "ta", "te", "tr", "ur", "uz",
+        ],
+        &["one", "other"],
+    ),
+    (&["ca", "es", "fr", "it", "pt"], &["one", "many", "other"]),
+    (&["bs", "hr", "ro", "sr"], &["one", "few", "other"]),
+    (
+        &["be", "cs", "lt", "pl", "ru", "sk", "uk"],
+        &["one", "few", "many", "other"],
+    ),
+    (&["he", "sl"], &["one", "two", "few", "other"]),
+    (&["ga"], &["one", "two", "few", "many", "other"]),
+    (
+        &["ar", "cy"],
+        &["zero", "one", "two", "few", "many", "other"],
+    ),
+];
+
+/// Returns the plural categories a language needs, or `None` for unknown languages.
+///
+/// Regions are ignored, so `pt-BR` and `pt_BR` use the rules of `pt`.
+pub fn get_plural_categories(language: &str) -> Option<&'static [&'static str]> {
+    let base_language = language
+        .split(|char| char == '-' || char == '_')
+        .next()?
+        .to_lowercase();
+
+    LANGUAGE_PLURAL_CATEGORIES
+        .iter()
+        .find(|(languages, _)| languages.contains(&base_language.as_str()))
+        .map(|(_, categories)| *categories)
+}
+
+/// Splits `item_one` (or `item.one`, depending on the separator) into `item` and `one`.
+/// Gettext plural forms are not supported, as `.po` files are not read.
+pub fn split_plural_category(key: &str, config: &PluralConfig) -> Option<(String, String)> {
+    let categories = config
+        .categories
+        .iter()
+        .map(|category| category.as_str())
+        .collect::<Vec<&str>>();
+
+    let (logical_key, category) = key.rsplit_once(config.separator.as_str())?;
+
+    if logical_key.is_empty() || !categories.contains(&category) {
+        return None;
+    }
+
+    Some((logical_key.to_string(), category.to_string()))
+}
+
+/// Returns the position of a category in the CLDR order, so variants can be sorted
+pub fn get_plural_category_order(category: &str) -> usize {
+    PLURAL_CATEGORIES
+        .iter()
+        .position(|plural_category| *plural_category == category)
+        .unwrap_or(PLURAL_CATEGORIES.len())
+}
+
+#[path = "./tests/plural_helper.rs"]
+#[cfg(test)]
+mod test;
@@ -0,0 +1,153 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use tower_lsp::lsp_types::{
+    ClientCapabilities, Diagnostic, DiagnosticOptions, DiagnosticServerCapabilities,
+    DocumentDiagnosticParams, DocumentDiagnosticReport, DocumentDiagnosticReportKind,
+    DocumentDiagnosticReportResult, FullDocumentDiagnosticReport,
+    RelatedFullDocumentDiagnosticReport, RelatedUnchangedDocumentDiagnosticReport,
+    UnchangedDocumentDiagnosticReport, WorkspaceDiagnosticParams, WorkspaceDiagnosticReport,
+    WorkspaceDiagnosticReportResult, WorkspaceDocumentDiagnosticReport,
+    WorkspaceFullDocumentDiagnosticReport, WorkspaceUnchangedDocumentDiagnosticReport,
+};
+
+use crate::diagnostic_helper::DIAGNOSTIC_SOURCE;
+use crate::Backend;
+
+/// The support of the client for pull diagnostics, read from its capabilities
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PullDiagnosticsSupport {
+    /// The server answers pull requests instead of publishing diagnostics
+    pub enabled: bool,
+    /// The client can be asked to pull the diagnostics again when they change
+    pub refresh: bool,
+}
+
+impl PullDiagnosticsSupport {
+    pub fn from_capabilities(capabilities: &ClientCapabilities) -> Self {
+        let enabled = capabilities
+            .text_document
+            .as_ref()
+            .is_some_and(|text_document| text_document.diagnostic.is_some());
+
+        // lsp-types reads the refresh support from `workspace.diagnostic` instead of the
+        // `workspace.diagnostics` that clients send, so it is assumed unless it is turned off there
+        let refresh = capabilities
+            .workspace
+            .as_ref()
+            .and_then(|workspace| workspace.diagnostic.as_ref())
+            .and_then(|diagnostic| diagnostic.refresh_support)
+            .unwrap_or(true);
+
+        PullDiagnosticsSupport {
+            enabled,
+            refresh: enabled && refresh,
+        }
+    }
+}
+
+/// The pull diagnostics capability, only advertised to clients that support it
+pub fn get_diagnostic_provider() -> DiagnosticServerCapabilities {
+    DiagnosticServerCapabilities::Options(DiagnosticOptions {
+        identifier: Some(DIAGNOSTIC_SOURCE.to_string()),
+        inter_file_dependencies: true,
+        workspace_diagnostics: true,
+        work_done_progress_options: Default::default(),
+    })
+}
+
+/// Identifies the diagnostics of a file, so they are only sent again when they change
+pub fn get_result_id(diagnostics: &[Diagnostic]) -> String {
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_string(diagnostics)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+
+    format!("{:016x}", hasher.finish())
+}
+
+pub fn get_report(
+    diagnostics: Vec<Diagnostic>,
+    previous_result_id: Option<&str>,
+) -> DocumentDiagnosticReportKind {
+    let result_id = get_result_id(&diagnostics);
+
+    if previous_result_id == Some(result_id.as_str()) {
+        DocumentDiagnosticReportKind::Unchanged(UnchangedDocumentDiagnosticReport { result_id })
+    } else {
+        DocumentDiagnosticReportKind::Full(FullDocumentDiagnosticReport {
+            result_id: Some(result_id),
+            items: diagnostics,
+        })
+    }
+}
+
+pub fn get_document_report(
+    backend: &Backend,
+    params: DocumentDiagnosticParams,
+) -> DocumentDiagnosticReportResult {
+    let report = get_report(
+        backend.get_file_diagnostics(&params.text_document.uri),
+        params.previous_result_id.as_deref(),
+    );
+
+    let report = match report {
+        DocumentDiagnosticReportKind::Full(report) => {
+            DocumentDiagnosticReport::Full(RelatedFullDocumentDiagnosticReport {
+                related_documents: None,
+                full_document_diagnostic_report: report,
+            })
+        }
+        DocumentDiagnosticReportKind::Unchanged(report) => {
+            DocumentDiagnosticReport::Unchanged(RelatedUnchangedDocumentDiagnosticReport {
+                related_documents: None,
+                unchanged_document_diagnostic_report: report,
+            })
+        }
+    };
+
+    DocumentDiagnosticReportResult::Report(report)
+}
+
+pub fn get_workspace_report(
+    backend: &Backend,
+    params: WorkspaceDiagnosticParams,
+) -> WorkspaceDiagnosticReportResult {
+    let items = backend
+        .get_workspace_diagnostics()
+        .into_iter()
+        .map(|(uri, diagnostics)| {
+            let previous_result_id = params
+                .previous_result_ids
+                .iter()
+                .find(|previous| previous.uri == uri)
+                .map(|previous| previous.value.as_str());
+            let version = backend.get_document_version(&uri);
+
+            match get_report(diagnostics, previous_result_id) {
+                DocumentDiagnosticReportKind::Full(report) => {
+                    WorkspaceDocumentDiagnosticReport::Full(WorkspaceFullDocumentDiagnosticReport {
+                        uri,
+                        version,
+                        full_document_diagnostic_report: report,
+                    })
+                }
+                DocumentDiagnosticReportKind::Unchanged(report) => {
+                    WorkspaceDocumentDiagnosticReport::Unchanged(
+                        WorkspaceUnchangedDocumentDiagnosticReport {
+                            uri,
+                            version,
+                            unchanged_document_diagnostic_report: report,
+                        },
+                    )
+                }
+            }
+        })
+        .collect();
+
+    WorkspaceDiagnosticReportResult::Report(WorkspaceDiagnosticReport { items })
+}
+
+#[path = "./tests/pull_diagnostics.rs"]
+#[cfg(test)]
+mod test;
@@ -3,6 +3,19 @@
    key: ((string) @translation_key)
    value: [
      (string (string_content) @translation_value)
-     (array (string) @translation_value)
+     (number) @translation_value
+     (true) @translation_value
+     (false) @translation_value
+     (null) @translation_value
    ]
 ) @translation_group
+(
+ array
+   [
+     (string (string_content) @translation_value)
+     (number) @translation_value
+     (true) @translation_value
+     (false) @translation_value
+     (null) @translation_value
+   ] @translation_key
+) @translation_group
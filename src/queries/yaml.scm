@@ -19,19 +19,15 @@
           (block_scalar) @translation_value
         )
       )
-      (
-        block_node (
-          (block_sequence
-            (block_sequence_item
-              (flow_node
-                (plain_scalar (string_scalar) @translation_value)
-              )
-            )
-          )
-        )
-      )
-
     ]
   )* @translation_group
 
-(boolean_scalar) @translation_error
+(block_sequence_item
+  (flow_node
+    [
+      (double_quote_scalar)
+      (single_quote_scalar)
+      (plain_scalar)
+    ] @translation_value
+  )
+) @translation_key @translation_group
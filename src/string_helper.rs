@@ -83,6 +83,14 @@ pub fn find_translation_key_by_position<'a>(
     None
 }
 
+/// Returns every translation key in the text, used to index where keys are used
+pub fn find_translation_keys(text: &str) -> Vec<regex::Match<'_>> {
+    TRANSLATION_REGEX
+        .captures_iter(text)
+        .map(|groups| groups.get(1).unwrap())
+        .collect()
+}
+
 pub fn get_editing_range(indexed_text: &IndexedText<String>, pos: &Pos) -> Option<Range<Pos>> {
     for groups in TRANSLATION_EDITING_REGEX.captures_iter(indexed_text.text()) {
         let result = groups.get(1).unwrap();
@@ -95,6 +103,163 @@ pub fn get_editing_range(indexed_text: &IndexedText<String>, pos: &Pos) -> Optio
     None
 }
 
+/// Returns the byte offset of `pos` in the text
+pub fn get_offset_of_position(indexed_text: &IndexedText<String>, pos: &Pos) -> usize {
+    indexed_text
+        .substr(Pos::new(0, 0)..*pos)
+        .map_or(0, |text_before| text_before.len())
+}
+
+/// Returns the length of the already completed segments of `typed_key`, including the last divider
+pub fn get_parent_key_length(typed_key: &str) -> usize {
+    typed_key
+        .rfind(TRANSLATION_KEY_DIVIDER)
+        .map_or(0, |index| index + TRANSLATION_KEY_DIVIDER.len())
+}
+
+/// Returns the segment of `key` that follows the segments already typed in `typed_key`,
+/// and whether that segment is a group containing more segments.
+///
+/// For `checkout.cart.title` and a `typed_key` of `checkout.ca` this returns `cart` as a group.
+pub fn get_next_key_segment(key: &str, typed_key: &str) -> Option<(String, bool)> {
+    let remainder = key.strip_prefix(&typed_key[..get_parent_key_length(typed_key)])?;
+    if remainder.is_empty() {
+        return None;
+    }
+
+    Some(match remainder.split_once(TRANSLATION_KEY_DIVIDER) {
+        Some((segment, _)) => (segment.to_string(), true),
+        None => (remainder.to_string(), false),
+    })
+}
+
+static SUBSTRING_MATCH_SCORE: usize = 1_000;
+static FUZZY_MATCH_SCORE: usize = 2_000;
+static VALUE_MATCH_SCORE: usize = 3_000;
+
+/// Scores how well `query` matches a key, where lower is better and `None` is no match.
+///
+/// Prefix matches rank above substring matches, which rank above fuzzy matches
+/// that only contain the characters of `query` in order.
+pub fn get_match_score(key: &str, query: &str) -> Option<usize> {
+    let key = key.to_lowercase();
+    let query = query.to_lowercase();
+
+    if key.starts_with(&query) {
+        return Some(0);
+    }
+
+    if let Some(index) = key.find(&query) {
+        return Some(SUBSTRING_MATCH_SCORE + index);
+    }
+
+    // Every character of the query has to appear in order, gaps make the match worse
+    let mut gaps = 0;
+    let mut key_chars = key.chars();
+    for query_char in query.chars() {
+        loop {
+            match key_chars.next() {
+                Some(key_char) if key_char == query_char => break,
+                Some(_) => gaps += 1,
+                None => return None,
+            }
+        }
+    }
+
+    Some(FUZZY_MATCH_SCORE + gaps.min(VALUE_MATCH_SCORE - FUZZY_MATCH_SCORE - 1))
+}
+
+/// Scores how well `query` matches a translation, which always ranks below key matches
+pub fn get_value_match_score(value: &str, query: &str) -> Option<usize> {
+    value
+        .to_lowercase()
+        .find(&query.to_lowercase())
+        .map(|index| VALUE_MATCH_SCORE + index)
+}
+
+/// Returns the range of the body of the object passed after a translation key,
+/// like `{ name: 'x' }` in `t('greeting', { name: 'x' })`.
+///
+/// The body ends at the closing brace, or at the end of the call while the object is being typed.
+pub fn find_interpolation_object(text: &str, key_end: usize) -> Option<Range<usize>> {
+    let after_key = text.get(key_end..)?;
+    let after_quote = after_key.get(after_key.chars().next()?.len_utf8()..)?;
+    let after_comma = after_quote.trim_start().strip_prefix(',')?.trim_start();
+    let body = after_comma.strip_prefix('{')?;
+
+    let start = text.len() - body.len();
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+
+    for (index, char) in body.char_indices() {
+        match (quote, char) {
+            (Some(quote_char), _) if char == quote_char => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"' | '`') => quote = Some(char),
+            (None, '{' | '[' | '(') => depth += 1,
+            (None, '}' | ']' | ')') if depth == 0 => return Some(start..start + index),
+            (None, '}' | ']' | ')') => depth -= 1,
+            _ => {}
+        }
+    }
+
+    Some(start..text.len())
+}
+
+/// Splits the body of an object into its top-level entries and returns
+/// how deeply nested the end of the body is
+pub fn get_object_entries(body: &str) -> (Vec<&str>, usize) {
+    let mut entries = vec![];
+    let mut entry_start = 0;
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+
+    for (index, char) in body.char_indices() {
+        match (quote, char) {
+            (Some(quote_char), _) if char == quote_char => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"' | '`') => quote = Some(char),
+            (None, '{' | '[' | '(') => depth += 1,
+            (None, '}' | ']' | ')') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                entries.push(&body[entry_start..index]);
+                entry_start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    entries.push(&body[entry_start..]);
+
+    (entries, depth)
+}
+
+/// Returns the names of the properties of an object body, or `None` when it spreads
+/// another object (`...props`) as its properties are unknown
+pub fn get_object_keys(body: &str) -> Option<Vec<String>> {
+    let mut keys = vec![];
+
+    for entry in get_object_entries(body).0 {
+        let entry = entry.trim();
+        if entry.starts_with("...") {
+            return None;
+        }
+
+        let key = entry
+            .split(':')
+            .next()
+            .unwrap_or("")
+            .trim()
+            .trim_matches(|char| char == '\'' || char == '"');
+
+        if !key.is_empty() {
+            keys.push(key.to_string());
+        }
+    }
+
+    Some(keys)
+}
+
 pub fn is_editing_position(indexed_text: &IndexedText<String>, pos: &Pos) -> bool {
     get_editing_range(indexed_text, pos).is_some()
 }
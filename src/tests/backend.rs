@@ -1,5 +1,4 @@
-use tower_lsp::jsonrpc::Response;
-use tower_lsp::jsonrpc::{Incoming, Outgoing};
+use tower_lsp::jsonrpc::{Request, Response};
 
 use core::task::Poll;
 
@@ -9,31 +8,37 @@ mod helpers;
 use helpers::*;
 
 lazy_static! {
-    static ref INITIALIZE_REQUEST: Incoming = serde_json::from_str(
+    static ref INITIALIZE_REQUEST: Request = serde_json::from_str(
         r#"{"jsonrpc":"2.0","method":"initialize","params":{"capabilities":{}},"id":1}"#
     )
     .unwrap();
 
 
-    static ref INITIALIZE_RESPONSE: Outgoing = Outgoing::Response(serde_json::from_str(r#"{
+    static ref INITIALIZE_RESPONSE: Response = serde_json::from_str(r#"{
             "jsonrpc":"2.0",
             "result": {
                 "capabilities": {
+                    "codeActionProvider": true,
+                    "codeLensProvider": {
+                        "resolveProvider": true
+                    },
                     "completionProvider": {
                         "resolveProvider": true,
                         "triggerCharacters": ["'", "\"", "`", "."]
                     },
+                    "documentSymbolProvider": true,
                     "hoverProvider": true,
                     "textDocumentSync": 2,
+                    "workspaceSymbolProvider": true,
                     "workspace": {"workspaceFolders": {"changeNotifications": true, "supported": true}}
                 }
             },
             "id":1
-        }"#).unwrap());
+        }"#).unwrap();
 
-    static ref INITIALIZED_REQUEST: Incoming = serde_json::from_str(r#"{"jsonrpc":"2.0","method":"initialized","params":{}}"#).unwrap();
+    static ref INITIALIZED_REQUEST: Request = serde_json::from_str(r#"{"jsonrpc":"2.0","method":"initialized","params":{}}"#).unwrap();
 
-    static ref WORKSPACE_CONFIGURATION_REQUEST: Incoming = serde_json::from_str(
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
         r#"{"jsonrpc":"2.0","result": [
     {
         "translationFiles": {
@@ -57,7 +62,7 @@ lazy_static! {
     )
     .unwrap();
 
-    static ref WORKSPACE_WORKSPACE_FOLDERS_REQUEST: Incoming = serde_json::from_str(
+    static ref WORKSPACE_WORKSPACE_FOLDERS_REQUEST: Response = serde_json::from_str(
         format!(
             r#"
             {{
@@ -95,7 +100,7 @@ async fn initialize() {
     );
 
     let raw = r#"{"jsonrpc":"2.0","error":{"code":-32600,"message":"Invalid request"},"id":1}"#;
-    let err = Outgoing::Response(serde_json::from_str::<Response>(raw).unwrap());
+    let err = serde_json::from_str::<Response>(raw).unwrap();
     assert_eq!(service.poll_ready(), Poll::Ready(Ok(())));
     assert_eq!(
         service.call(INITIALIZE_REQUEST.clone()).await,
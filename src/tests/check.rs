@@ -0,0 +1,176 @@
+use super::*;
+
+use pretty_assertions::assert_eq;
+use tower_lsp::lsp_types::{Position, Range};
+
+fn to_args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+fn check_fixture() -> Vec<FileDiagnostics> {
+    check(&CheckArgs {
+        root: PathBuf::from("src/tests/fixtures/check"),
+        config: None,
+        format: OutputFormat::Text,
+    })
+    .unwrap()
+}
+
+fn create_file(path: &str, severity: DiagnosticSeverity, code: &str) -> FileDiagnostics {
+    FileDiagnostics {
+        path: path.to_string(),
+        diagnostics: vec![Diagnostic {
+            range: Range::new(Position::new(1, 2), Position::new(1, 8)),
+            severity: Some(severity),
+            code: Some(NumberOrString::String(code.to_string())),
+            message: "`title` is too long".to_string(),
+            ..Default::default()
+        }],
+    }
+}
+
+#[test]
+fn parses_args() {
+    assert_eq!(
+        parse_args(&to_args(&[
+            "--config",
+            "ci.json",
+            "--format",
+            "sarif",
+            "packages/app"
+        ])),
+        Ok(CheckArgs {
+            root: PathBuf::from("packages/app"),
+            config: Some(PathBuf::from("ci.json")),
+            format: OutputFormat::Sarif,
+        })
+    );
+    assert_eq!(
+        parse_args(&[]),
+        Ok(CheckArgs {
+            root: PathBuf::from("."),
+            config: None,
+            format: OutputFormat::Text,
+        })
+    );
+}
+
+#[test]
+fn rejects_invalid_args() {
+    assert!(parse_args(&to_args(&["--format", "xml"])).is_err());
+    assert!(parse_args(&to_args(&["--config"])).is_err());
+    assert!(parse_args(&to_args(&["--verbose"])).is_err());
+    assert!(parse_args(&to_args(&["a", "b"])).is_err());
+}
+
+#[test]
+fn checks_translation_files_and_source_files() {
+    let files = check_fixture();
+
+    let codes = files
+        .iter()
+        .map(|file| {
+            (
+                file.path.as_str(),
+                file.diagnostics
+                    .iter()
+                    .filter_map(get_code)
+                    .collect::<Vec<String>>(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        codes,
+        vec![
+            ("index.js", vec!["missing-key".to_string()]),
+            ("locales/en.json", vec!["unused-key".to_string()]),
+            ("locales/nl.json", vec!["unused-key".to_string()]),
+        ]
+    );
+    assert!(has_errors(&files));
+}
+
+#[test]
+fn fails_without_config() {
+    let result = check(&CheckArgs {
+        root: PathBuf::from("src/tests/fixtures/check"),
+        config: Some(PathBuf::from("src/tests/fixtures/check/missing.json")),
+        format: OutputFormat::Text,
+    });
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn formats_text() {
+    let files = vec![
+        create_file("en.json", DiagnosticSeverity::WARNING, "max-length"),
+        create_file("nl.json", DiagnosticSeverity::ERROR, "max-length"),
+    ];
+
+    assert_eq!(
+        format_text(&files),
+        [
+            "en.json:2:3: warning[max-length]: `title` is too long",
+            "nl.json:2:3: error[max-length]: `title` is too long",
+            "1 errors, 1 warnings",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn formats_json() {
+    let files = vec![create_file(
+        "en.json",
+        DiagnosticSeverity::WARNING,
+        "max-length",
+    )];
+
+    assert_eq!(
+        format_json(&files),
+        json!([{
+            "file": "en.json",
+            "range": {
+                "start": { "line": 1, "character": 2 },
+                "end": { "line": 1, "character": 8 },
+            },
+            "severity": "warning",
+            "code": "max-length",
+            "message": "`title` is too long",
+        }])
+    );
+}
+
+#[test]
+fn formats_sarif() {
+    let files = vec![
+        create_file("en.json", DiagnosticSeverity::HINT, "untranslated"),
+        create_file("nl.json", DiagnosticSeverity::ERROR, "max-length"),
+    ];
+
+    let sarif = format_sarif(&files);
+    let run = &sarif["runs"][0];
+
+    assert_eq!(sarif["version"], json!("2.1.0"));
+    assert_eq!(
+        run["tool"]["driver"]["rules"],
+        json!([{ "id": "max-length" }, { "id": "untranslated" }])
+    );
+    assert_eq!(run["results"][0]["level"], json!("note"));
+    assert_eq!(
+        run["results"][1],
+        json!({
+            "ruleId": "max-length",
+            "level": "error",
+            "message": { "text": "`title` is too long" },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": { "uri": "nl.json" },
+                    "region": { "startLine": 2, "startColumn": 3, "endLine": 2, "endColumn": 9 },
+                },
+            }],
+        })
+    );
+}
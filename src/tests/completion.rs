@@ -1,4 +1,4 @@
-use tower_lsp::jsonrpc::{Incoming, Outgoing};
+use tower_lsp::jsonrpc::{Request, Response};
 
 mod helpers;
 use helpers::*;
@@ -6,7 +6,7 @@ use helpers::*;
 // use helpers;
 
 lazy_static! {
-    static ref DID_OPEN_REQUEST: Incoming = serde_json::from_str(r#"{
+    static ref DID_OPEN_REQUEST: Request = serde_json::from_str(r#"{
             "jsonrpc":"2.0",
             "method":"textDocument/didOpen",
             "params":{
@@ -16,11 +16,10 @@ lazy_static! {
                     "version": 1,
                     "text": "translate('')"
                 }
-            },
-            "id":1
+            }
         }"#).unwrap();
 
-    static ref COMPLETION_REQUEST: Incoming = serde_json::from_str(r#"{
+    static ref COMPLETION_REQUEST: Request = serde_json::from_str(r#"{
             "jsonrpc":"2.0",
             "method":"textDocument/completion",
             "params":{
@@ -38,13 +37,15 @@ lazy_static! {
             "id":1
         }"#).unwrap();
 
-    static ref COMPLETION_RESPONSE: Outgoing =  Outgoing::Response(serde_json::from_str(r#"
+    static ref COMPLETION_RESPONSE: Response =  serde_json::from_str(r#"
 {
   "jsonrpc": "2.0",
   "result": [
     {
       "kind": 1,
       "label": "main.header.title",
+      "detail": "This title will appear in the header.",
+      "labelDetails": { "description": "This title will appear in the header." },
       "textEdit": {
           "newText": "main.header.title",
           "range": {
@@ -56,6 +57,8 @@ lazy_static! {
     {
       "kind": 1,
       "label": "main.content.heading.title",
+      "detail": "A regular header for my content",
+      "labelDetails": { "description": "A regular header for my content" },
       "textEdit": {
           "newText": "main.content.heading.title",
           "range": {
@@ -67,6 +70,8 @@ lazy_static! {
     {
       "kind": 1,
       "label": "main.content.heading.body",
+      "detail": "This is the body of my website.",
+      "labelDetails": { "description": "This is the body of my website." },
       "textEdit": {
           "newText": "main.content.heading.body",
           "range": {
@@ -79,10 +84,10 @@ lazy_static! {
   ],
   "id": 1
 }
-"#).unwrap());
+"#).unwrap();
 
     // TODO: Add this test
-    /* static ref COMPLETION_RESOLVE_REQUEST: Incoming = serde_json::from_str(r#"{
+    /* static ref COMPLETION_RESOLVE_REQUEST: Request = serde_json::from_str(r#"{
             "jsonrpc": "2.0",
             "method": "completionItem/resolve",
             "params": {
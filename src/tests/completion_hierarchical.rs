@@ -0,0 +1,179 @@
+use tower_lsp::jsonrpc::{Request, Response};
+
+mod helpers;
+use helpers::*;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+lazy_static! {
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/*.yml"
+            ]
+        },
+        "fileName": {
+            "details": ""
+        },
+        "key": {
+            "details": "^.+?\\.(?P<language>.+?)\\.",
+            "filter": "^.+?\\.(.+$)"
+        },
+        "completion": {
+            "hierarchical": true
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+    static ref DID_OPEN_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/didOpen",
+            "params":{
+                "textDocument": {
+                    "uri": "file:///somefile.js",
+                    "languageId": "javascript",
+                    "version": 1,
+                    "text": "translate('simple_form.')"
+                }
+            }
+        }"#
+    )
+    .unwrap();
+    static ref COMPLETION_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/completion",
+            "params":{
+                "textDocument": {
+                    "uri": "file:///somefile.js"
+                },
+                "position": {
+                    "line": 0,
+                    "character": 23
+                },
+                "context": {
+                    "triggerKind": 2,
+                    "triggerCharacter": "."
+                }
+            },
+            "id": 2
+        }"#
+    )
+    .unwrap();
+    static ref COMPLETION_RESPONSE: Response = serde_json::from_str(r#"
+{
+  "jsonrpc": "2.0",
+  "result": [
+    {
+      "kind": 1,
+      "label": "no",
+      "detail": "No",
+      "labelDetails": { "description": "No" },
+      "textEdit": {
+          "newText": "no",
+          "range": {
+              "start": { "character": 23, "line": 0 },
+              "end": { "character": 23, "line": 0 }
+          }
+      },
+      "data": { "namespace": null, "key": "simple_form.no" }
+    },
+    {
+      "kind": 9,
+      "label": "required",
+      "textEdit": {
+          "newText": "required.",
+          "range": {
+              "start": { "character": 23, "line": 0 },
+              "end": { "character": 23, "line": 0 }
+          }
+      },
+      "command": {
+          "title": "Suggest next segment",
+          "command": "editor.action.triggerSuggest"
+      }
+    },
+    {
+      "kind": 1,
+      "label": "new_model",
+      "detail": "%{model} toevoegen",
+      "labelDetails": { "description": "%{model} toevoegen" },
+      "textEdit": {
+          "newText": "new_model",
+          "range": {
+              "start": { "character": 23, "line": 0 },
+              "end": { "character": 23, "line": 0 }
+          }
+      },
+      "data": { "namespace": null, "key": "simple_form.new_model" }
+    },
+    {
+      "kind": 1,
+      "label": "confirm_registration",
+      "detail": ">\n      Let op!\n      Dit is meerdere regels",
+      "labelDetails": { "description": "> Let op! Dit is meerdere regels" },
+      "textEdit": {
+          "newText": "confirm_registration",
+          "range": {
+              "start": { "character": 23, "line": 0 },
+              "end": { "character": 23, "line": 0 }
+          }
+      },
+      "data": { "namespace": null, "key": "simple_form.confirm_registration" }
+    },
+    {
+      "kind": 9,
+      "label": "date",
+      "textEdit": {
+          "newText": "date.",
+          "range": {
+              "start": { "character": 23, "line": 0 },
+              "end": { "character": 23, "line": 0 }
+          }
+      },
+      "command": {
+          "title": "Suggest next segment",
+          "command": "editor.action.triggerSuggest"
+      }
+    },
+    {
+      "kind": 9,
+      "label": "formats",
+      "textEdit": {
+          "newText": "formats.",
+          "range": {
+              "start": { "character": 23, "line": 0 },
+              "end": { "character": 23, "line": 0 }
+          }
+      },
+      "command": {
+          "title": "Suggest next segment",
+          "command": "editor.action.triggerSuggest"
+      }
+    }
+  ],
+  "id": 2
+}
+"#).unwrap();
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn completion_offers_next_segments() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    assert_eq!(service.call(DID_OPEN_REQUEST.clone()).await, Ok(None));
+
+    assert_eq!(
+        service.call(COMPLETION_REQUEST.clone()).await,
+        Ok(Some(COMPLETION_RESPONSE.clone()))
+    );
+}
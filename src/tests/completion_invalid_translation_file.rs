@@ -1,4 +1,4 @@
-use tower_lsp::jsonrpc::{Incoming, Outgoing};
+use tower_lsp::jsonrpc::{Request, Response};
 
 mod helpers;
 use helpers::*;
@@ -7,7 +7,7 @@ use helpers::*;
 use pretty_assertions::assert_eq;
 
 lazy_static! {
-    static ref WORKSPACE_CONFIGURATION_REQUEST: Incoming = serde_json::from_str(
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
         r#"{"jsonrpc":"2.0","result": [
     {
         "translationFiles": {
@@ -27,7 +27,7 @@ lazy_static! {
 ], "id": 1 }"#
     )
     .unwrap();
-    static ref DID_OPEN_REQUEST: Incoming = serde_json::from_str(
+    static ref DID_OPEN_REQUEST: Request = serde_json::from_str(
         r#"{
             "jsonrpc":"2.0",
             "method":"textDocument/didOpen",
@@ -38,12 +38,11 @@ lazy_static! {
                     "version": 1,
                     "text": "translate('')"
                 }
-            },
-            "id": 2
+            }
         }"#
     )
     .unwrap();
-    static ref COMPLETION_REQUEST: Incoming = serde_json::from_str(
+    static ref COMPLETION_REQUEST: Request = serde_json::from_str(
         r#"{
             "jsonrpc":"2.0",
             "method":"textDocument/completion",
@@ -63,18 +62,43 @@ lazy_static! {
         }"#
     )
     .unwrap();
-    static ref COMPLETION_RESPONSE: Outgoing = Outgoing::Response(
-        serde_json::from_str(
-            r#"
+    static ref COMPLETION_RESPONSE: Response = serde_json::from_str(
+        r#"
 {
    "jsonrpc":"2.0",
-   "result":[],
+   "result":[
+    {
+      "kind": 1,
+      "label": "test",
+      "detail": "This should not be included as the line below contains a boolean",
+      "labelDetails": { "description": "This should not be included as the line below contains a bo…" },
+      "textEdit": {
+          "newText": "test",
+          "range": {
+              "start": { "character": 11, "line": 0 },
+              "end": { "character": 11, "line": 0 }
+          }
+      }
+    },
+    {
+      "kind": 1,
+      "label": "error",
+      "detail": "true",
+      "labelDetails": { "description": "true" },
+      "textEdit": {
+          "newText": "error",
+          "range": {
+              "start": { "character": 11, "line": 0 },
+              "end": { "character": 11, "line": 0 }
+          }
+      }
+    }
+   ],
    "id": 2
 }
 "#
-        )
-        .unwrap()
-    );
+    )
+    .unwrap();
 }
 
 #[tokio::test]
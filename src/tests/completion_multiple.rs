@@ -1,4 +1,4 @@
-use tower_lsp::jsonrpc::{Incoming, Outgoing};
+use tower_lsp::jsonrpc::{Request, Response};
 
 mod helpers;
 use helpers::*;
@@ -9,7 +9,7 @@ use pretty_assertions::assert_eq;
 // use helpers;
 
 lazy_static! {
-    static ref WORKSPACE_CONFIGURATION_REQUEST: Incoming = serde_json::from_str(
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
         r#"{"jsonrpc":"2.0","result": [
     {
         "translationFiles": {
@@ -32,7 +32,7 @@ lazy_static! {
 ], "id": 1 }"#
     )
     .unwrap();
-    static ref DID_OPEN_REQUEST: Incoming = serde_json::from_str(
+    static ref DID_OPEN_REQUEST: Request = serde_json::from_str(
         r#"{
             "jsonrpc":"2.0",
             "method":"textDocument/didOpen",
@@ -43,12 +43,11 @@ lazy_static! {
                     "version": 1,
                     "text": "translate('')"
                 }
-            },
-            "id": 2
+            }
         }"#
     )
     .unwrap();
-    static ref COMPLETION_REQUEST: Incoming = serde_json::from_str(
+    static ref COMPLETION_REQUEST: Request = serde_json::from_str(
         r#"{
             "jsonrpc":"2.0",
             "method":"textDocument/completion",
@@ -68,9 +67,8 @@ lazy_static! {
         }"#
     )
     .unwrap();
-    static ref COMPLETION_RESPONSE: Outgoing = Outgoing::Response(
-        serde_json::from_str(
-            r#"
+    static ref COMPLETION_RESPONSE: Response = serde_json::from_str(
+        r#"
 {
    "jsonrpc":"2.0",
    "result":[
@@ -231,9 +229,8 @@ lazy_static! {
    "id": 2
 }
 "#
-        )
-        .unwrap()
-    );
+    )
+    .unwrap();
 }
 
 #[tokio::test]
@@ -0,0 +1,168 @@
+use tower_lsp::{
+    jsonrpc::{Request, Response},
+    lsp_types::{
+        CompletionItem, CompletionItemKind, CompletionItemLabelDetails, CompletionTextEdit,
+        Position, TextEdit,
+    },
+};
+
+mod helpers;
+use helpers::*;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+lazy_static! {
+    static ref WORKSPACE_CONFIGURATION_REQUEST_JSON: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/non_string_values/*.json"
+            ]
+        },
+        "fileName": {
+            "details": ""
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+    static ref WORKSPACE_CONFIGURATION_REQUEST_YML: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/non_string_values/*.yml"
+            ]
+        },
+        "fileName": {
+            "details": ""
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+    static ref DID_OPEN_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/didOpen",
+            "params":{
+                "textDocument": {
+                    "uri": "file:///somefile.js",
+                    "languageId": "javascript",
+                    "version": 1,
+                    "text": "translate('')"
+                }
+            }
+        }"#
+    )
+    .unwrap();
+    static ref COMPLETION_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/completion",
+            "params":{
+                "textDocument": {
+                    "uri": "file:///somefile.js"
+                },
+                "position": {
+                    "line": 0,
+                    "character": 11
+                },
+                "context": {
+                    "triggerKind": 1
+                }
+            },
+            "id": 2
+        }"#
+    )
+    .unwrap();
+    static ref COMPLETION_RESPONSE_JSON: Response = completion_response(&[
+        ("enabled", "true"),
+        ("count", "3"),
+        ("ratio", "1.5"),
+        ("missing", "null"),
+        ("title", "Title"),
+        ("list[0]", "first"),
+        ("list[1][0]", "nested"),
+        ("items[0].name", "Item"),
+    ]);
+    static ref COMPLETION_RESPONSE_YML: Response = completion_response(&[
+        ("enabled", "true"),
+        ("count", "3"),
+        ("missing", "~"),
+        ("title", "Title"),
+        ("list[0]", "first"),
+        ("list[1][0]", "nested"),
+        ("items[0].name", "Item"),
+    ]);
+}
+
+fn completion_response(keys: &[(&str, &str)]) -> Response {
+    let completion_items = keys
+        .iter()
+        .map(|(key, detail)| CompletionItem {
+            label: key.to_string(),
+            kind: Some(CompletionItemKind::TEXT),
+            detail: Some(detail.to_string()),
+            label_details: Some(CompletionItemLabelDetails {
+                detail: None,
+                description: Some(detail.to_string()),
+            }),
+            text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+                range: tower_lsp::lsp_types::Range::new(
+                    Position {
+                        line: 0,
+                        character: 11,
+                    },
+                    Position {
+                        line: 0,
+                        character: 11,
+                    },
+                ),
+                new_text: key.to_string(),
+            })),
+            ..Default::default()
+        })
+        .collect::<Vec<CompletionItem>>();
+
+    Response::from_ok(
+        tower_lsp::jsonrpc::Id::Number(2),
+        serde_json::to_value(completion_items).unwrap(),
+    )
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn completion_json() {
+    let (mut service, _) =
+        prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST_JSON).await;
+
+    assert_eq!(service.call(DID_OPEN_REQUEST.clone()).await, Ok(None));
+
+    assert_eq!(
+        service.call(COMPLETION_REQUEST.clone()).await,
+        Ok(Some(COMPLETION_RESPONSE_JSON.clone()))
+    );
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn completion_yml() {
+    let (mut service, _) =
+        prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST_YML).await;
+
+    assert_eq!(service.call(DID_OPEN_REQUEST.clone()).await, Ok(None));
+
+    assert_eq!(
+        service.call(COMPLETION_REQUEST.clone()).await,
+        Ok(Some(COMPLETION_RESPONSE_YML.clone()))
+    );
+}
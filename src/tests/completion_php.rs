@@ -1,6 +1,9 @@
 use tower_lsp::{
-    jsonrpc::{Incoming, Outgoing, Response},
-    lsp_types::{CompletionItem, CompletionItemKind, CompletionTextEdit, Position, TextEdit},
+    jsonrpc::{Request, Response},
+    lsp_types::{
+        CompletionItem, CompletionItemKind, CompletionItemLabelDetails, CompletionTextEdit,
+        Position, TextEdit,
+    },
 };
 
 mod helpers;
@@ -12,7 +15,7 @@ use pretty_assertions::assert_eq;
 // use helpers;
 
 lazy_static! {
-    static ref WORKSPACE_CONFIGURATION_REQUEST: Incoming = serde_json::from_str(
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
         r#"{"jsonrpc":"2.0","result": [
     {
         "translationFiles": {
@@ -30,7 +33,7 @@ lazy_static! {
 ], "id": 1 }"#
     )
     .unwrap();
-    static ref DID_OPEN_REQUEST: Incoming = serde_json::from_str(
+    static ref DID_OPEN_REQUEST: Request = serde_json::from_str(
         r#"{
             "jsonrpc":"2.0",
             "method":"textDocument/didOpen",
@@ -41,12 +44,11 @@ lazy_static! {
                     "version": 1,
                     "text": "translate('')"
                 }
-            },
-            "id": 2
+            }
         }"#
     )
     .unwrap();
-    static ref COMPLETION_REQUEST: Incoming = serde_json::from_str(
+    static ref COMPLETION_REQUEST: Request = serde_json::from_str(
         r#"{
             "jsonrpc":"2.0",
             "method":"textDocument/completion",
@@ -66,18 +68,22 @@ lazy_static! {
         }"#
     )
     .unwrap();
-    static ref COMPLETION_RESPONSE: Outgoing = {
+    static ref COMPLETION_RESPONSE: Response = {
         let keys = [
-            "test-single",
-            "test-multiline",
+            ("test-single", "A translation"),
+            ("test-multiline", "a translation on multiple lines"),
         ];
 
         let completion_items = keys
             .iter()
-            .map(|key| CompletionItem {
+            .map(|(key, detail)| CompletionItem {
                 label: key.to_string(),
-                kind: Some(CompletionItemKind::Text),
-                detail: None,
+                kind: Some(CompletionItemKind::TEXT),
+                detail: Some(detail.to_string()),
+                label_details: Some(CompletionItemLabelDetails {
+                    detail: None,
+                    description: Some(detail.to_string()),
+                }),
                 text_edit: Some(CompletionTextEdit::Edit(TextEdit {
                     range: tower_lsp::lsp_types::Range::new(
                         Position {
@@ -95,10 +101,10 @@ lazy_static! {
             })
             .collect::<Vec<CompletionItem>>();
 
-        Outgoing::Response(Response::ok(
+        Response::from_ok(
             tower_lsp::jsonrpc::Id::Number(2),
             serde_json::to_value(completion_items).unwrap(),
-        ))
+        )
     };
 }
 
@@ -0,0 +1,109 @@
+use tower_lsp::jsonrpc::{Request, Response};
+
+mod helpers;
+use helpers::*;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+lazy_static! {
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/per_language_file/*.json"
+            ]
+        },
+        "fileName": {
+            "details": "(?P<language>.+?)\\."
+        },
+        "completion": {
+            "preferredLanguage": "en"
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+    static ref DID_OPEN_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/didOpen",
+            "params":{
+                "textDocument": {
+                    "uri": "file:///somefile.js",
+                    "languageId": "javascript",
+                    "version": 1,
+                    "text": "translate('')"
+                }
+            }
+        }"#
+    )
+    .unwrap();
+    static ref COMPLETION_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/completion",
+            "params":{
+                "textDocument": {
+                    "uri": "file:///somefile.js"
+                },
+                "position": {
+                    "line": 0,
+                    "character": 11
+                },
+                "context": {
+                    "triggerKind": 1
+                }
+            },
+            "id": 2
+        }"#
+    )
+    .unwrap();
+    static ref COMPLETION_RESPONSE: Response = serde_json::from_str(
+        r#"
+{
+   "jsonrpc":"2.0",
+   "result":[
+      {
+         "kind":1,
+         "label":"test",
+         "detail":"English",
+         "labelDetails": { "description": "English" },
+         "textEdit":{
+            "newText":"test",
+            "range":{
+               "start":{
+                  "character":11,
+                  "line":0
+               },
+               "end":{
+                  "character":11,
+                  "line":0
+               }
+            }
+         }
+      }
+   ],
+   "id": 2
+}
+"#
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn completion_previews_preferred_language() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    assert_eq!(service.call(DID_OPEN_REQUEST.clone()).await, Ok(None));
+
+    assert_eq!(
+        service.call(COMPLETION_REQUEST.clone()).await,
+        Ok(Some(COMPLETION_RESPONSE.clone()))
+    );
+}
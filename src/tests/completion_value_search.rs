@@ -0,0 +1,148 @@
+use tower_lsp::jsonrpc::{Request, Response};
+
+mod helpers;
+use helpers::*;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+lazy_static! {
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/translations.json"
+            ]
+        },
+        "fileName": {
+            "details": ""
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+    static ref OFF_WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/translations.json"
+            ]
+        },
+        "fileName": {
+            "details": ""
+        },
+        "completion": {
+            "valueSearch": "off"
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+    static ref DID_OPEN_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/didOpen",
+            "params":{
+                "textDocument": {
+                    "uri": "file:///somefile.js",
+                    "languageId": "javascript",
+                    "version": 1,
+                    "text": "translate('website')"
+                }
+            }
+        }"#
+    )
+    .unwrap();
+    static ref COMPLETION_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/completion",
+            "params":{
+                "textDocument": {
+                    "uri": "file:///somefile.js"
+                },
+                "position": {
+                    "line": 0,
+                    "character": 18
+                },
+                "context": {
+                    "triggerKind": 1
+                }
+            },
+            "id": 2
+        }"#
+    )
+    .unwrap();
+    static ref COMPLETION_RESPONSE: Response = serde_json::from_str(
+        r#"
+{
+   "jsonrpc":"2.0",
+   "result":{
+      "isIncomplete":true,
+      "items":[
+         {
+            "kind":1,
+            "label":"1234567890.en-us.main.content.heading.body",
+            "detail":"This is the body of my website.",
+            "labelDetails": { "description": "This is the body of my website." },
+            "sortText":"0000003023",
+            "filterText":"website",
+            "textEdit":{
+               "newText":"1234567890.en-us.main.content.heading.body",
+               "range":{
+                  "start":{
+                     "character":11,
+                     "line":0
+                  },
+                  "end":{
+                     "character":18,
+                     "line":0
+                  }
+               }
+            }
+         }
+      ]
+   },
+   "id": 2
+}
+"#
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn completion_matches_translation_value() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    assert_eq!(service.call(DID_OPEN_REQUEST.clone()).await, Ok(None));
+
+    assert_eq!(
+        service.call(COMPLETION_REQUEST.clone()).await,
+        Ok(Some(COMPLETION_RESPONSE.clone()))
+    );
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn completion_does_not_match_translation_value_when_turned_off() {
+    let (mut service, _) =
+        prepare_with_workspace_config(&OFF_WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    assert_eq!(service.call(DID_OPEN_REQUEST.clone()).await, Ok(None));
+
+    let response = service.call(COMPLETION_REQUEST.clone()).await.unwrap();
+    let response = serde_json::to_value(response).unwrap();
+
+    // Every key is offered in a complete list, the client filters them by key
+    assert_eq!(response["result"].as_array().map(Vec::len), Some(3));
+    assert!(response["result"][0].get("filterText").is_none());
+}
@@ -1,6 +1,9 @@
 use tower_lsp::{
-    jsonrpc::{Incoming, Outgoing, Response},
-    lsp_types::{CompletionItem, CompletionItemKind, CompletionTextEdit, Position, TextEdit},
+    jsonrpc::{Request, Response},
+    lsp_types::{
+        CompletionItem, CompletionItemKind, CompletionItemLabelDetails, CompletionTextEdit,
+        Position, TextEdit,
+    },
 };
 
 mod helpers;
@@ -12,7 +15,7 @@ use pretty_assertions::assert_eq;
 // use helpers;
 
 lazy_static! {
-    static ref WORKSPACE_CONFIGURATION_REQUEST: Incoming = serde_json::from_str(
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
         r#"{"jsonrpc":"2.0","result": [
     {
         "translationFiles": {
@@ -34,7 +37,7 @@ lazy_static! {
 ], "id": 1 }"#
     )
     .unwrap();
-    static ref DID_OPEN_REQUEST: Incoming = serde_json::from_str(
+    static ref DID_OPEN_REQUEST: Request = serde_json::from_str(
         r#"{
             "jsonrpc":"2.0",
             "method":"textDocument/didOpen",
@@ -45,12 +48,11 @@ lazy_static! {
                     "version": 1,
                     "text": "translate('')"
                 }
-            },
-            "id": 2
+            }
         }"#
     )
     .unwrap();
-    static ref COMPLETION_REQUEST: Incoming = serde_json::from_str(
+    static ref COMPLETION_REQUEST: Request = serde_json::from_str(
         r#"{
             "jsonrpc":"2.0",
             "method":"textDocument/completion",
@@ -70,25 +72,33 @@ lazy_static! {
         }"#
     )
     .unwrap();
-    static ref COMPLETION_RESPONSE: Outgoing = {
+    static ref COMPLETION_RESPONSE: Response = {
         let keys = [
-            "accounts.edit.new_password",
-            "accounts.edit.update",
-            "employees.assigned_employees.assigned_employee.main_dta",
-            "simple_form.no",
-            "simple_form.required.mark",
-            "simple_form.new_model",
-            "simple_form.confirm_registration",
-            "simple_form.date.abbr_day_names[0]",
-            "simple_form.formats.default",
+            ("accounts.edit.new_password", "ABC"),
+            ("accounts.edit.update", "HIJK"),
+            ("employees.assigned_employees.assigned_employee.main_dta", "LMNOP"),
+            ("simple_form.no", "No"),
+            ("simple_form.required.mark", "*"),
+            ("simple_form.new_model", "%{model} toevoegen"),
+            (
+                "simple_form.confirm_registration",
+                ">\n      Let op!\n      Dit is meerdere regels",
+            ),
+            ("simple_form.date.abbr_day_names[0]", "Zo"),
+            ("simple_form.formats.default", "%d/%m/%Y"),
         ];
 
         let completion_items = keys
             .iter()
-            .map(|key| CompletionItem {
+            .map(|(key, detail)| CompletionItem {
                 label: key.to_string(),
-                kind: Some(CompletionItemKind::Text),
-                detail: None,
+                kind: Some(CompletionItemKind::TEXT),
+                detail: Some(detail.to_string()),
+                // The preview shows the value on a single line
+                label_details: Some(CompletionItemLabelDetails {
+                    detail: None,
+                    description: Some(detail.split_whitespace().collect::<Vec<_>>().join(" ")),
+                }),
                 text_edit: Some(CompletionTextEdit::Edit(TextEdit {
                     range: tower_lsp::lsp_types::Range::new(
                         Position {
@@ -106,10 +116,10 @@ lazy_static! {
             })
             .collect::<Vec<CompletionItem>>();
 
-        Outgoing::Response(Response::ok(
+        Response::from_ok(
             tower_lsp::jsonrpc::Id::Number(2),
             serde_json::to_value(completion_items).unwrap(),
-        ))
+        )
     };
 }
 
@@ -0,0 +1,179 @@
+use tower_lsp::{
+    jsonrpc::{Request, Response},
+    lsp_types::{
+        CompletionItem, CompletionItemKind, CompletionItemLabelDetails, CompletionTextEdit,
+        Position, TextEdit,
+    },
+};
+
+mod helpers;
+use helpers::*;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+lazy_static! {
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/yaml_anchors/*.yml"
+            ]
+        },
+        "fileName": {
+            "details": ""
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+    static ref DID_OPEN_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/didOpen",
+            "params":{
+                "textDocument": {
+                    "uri": "file:///somefile.js",
+                    "languageId": "javascript",
+                    "version": 1,
+                    "text": "translate('')\ntranslate('page.title')"
+                }
+            }
+        }"#
+    )
+    .unwrap();
+    static ref COMPLETION_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/completion",
+            "params":{
+                "textDocument": {
+                    "uri": "file:///somefile.js"
+                },
+                "position": {
+                    "line": 0,
+                    "character": 11
+                },
+                "context": {
+                    "triggerKind": 1
+                }
+            },
+            "id": 2
+        }"#
+    )
+    .unwrap();
+    static ref COMPLETION_RESPONSE: Response = {
+        let keys = [
+            ("defaults.title", "Default title"),
+            ("defaults.body", "Default body"),
+            ("page.body", "Page body"),
+            ("greeting", "Hello"),
+            ("second.title", "Second document"),
+            ("page.title", "Default title"),
+            ("copy.title", "Default title"),
+            ("copy.body", "Default body"),
+            ("welcome", "Hello"),
+        ];
+
+        let completion_items = keys
+            .iter()
+            .map(|(key, detail)| CompletionItem {
+                label: key.to_string(),
+                kind: Some(CompletionItemKind::TEXT),
+                detail: Some(detail.to_string()),
+                label_details: Some(CompletionItemLabelDetails {
+                    detail: None,
+                    description: Some(detail.to_string()),
+                }),
+                text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+                    range: tower_lsp::lsp_types::Range::new(
+                        Position {
+                            line: 0,
+                            character: 11,
+                        },
+                        Position {
+                            line: 0,
+                            character: 11,
+                        },
+                    ),
+                    new_text: key.to_string(),
+                })),
+                ..Default::default()
+            })
+            .collect::<Vec<CompletionItem>>();
+
+        Response::from_ok(
+            tower_lsp::jsonrpc::Id::Number(2),
+            serde_json::to_value(completion_items).unwrap(),
+        )
+    };
+    static ref HOVER_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/hover",
+            "params": {
+                "textDocument": {
+                    "uri": "file:///somefile.js"
+                },
+                "position": {
+                    "line": 1,
+                    "character": 12
+                }
+            },
+            "id":1
+        }"#
+    )
+    .unwrap();
+    static ref HOVER_RESPONSE: Response = serde_json::from_str(
+        r#"
+{
+   "jsonrpc":"2.0",
+   "result":{
+      "contents":"|translation|\n|-|\n|Default title|",
+      "range":{
+         "end":{
+            "character": 21,
+            "line": 1
+         },
+         "start":{
+            "character": 11,
+            "line": 1
+         }
+      }
+   },
+   "id":1
+}
+"#
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn completion_includes_aliased_keys() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    assert_eq!(service.call(DID_OPEN_REQUEST.clone()).await, Ok(None));
+
+    assert_eq!(
+        service.call(COMPLETION_REQUEST.clone()).await,
+        Ok(Some(COMPLETION_RESPONSE.clone()))
+    );
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn hover_on_merged_key() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    assert_eq!(service.call(DID_OPEN_REQUEST.clone()).await, Ok(None));
+
+    assert_eq!(
+        service.call(HOVER_REQUEST.clone()).await,
+        Ok(Some(HOVER_RESPONSE.clone()))
+    );
+}
@@ -61,3 +61,33 @@ fn printable_value_doesnt_escape_arabic() {
 
     assert_eq!(definition.get_printable_value(), "مهلا");
 }
+
+#[test]
+fn preview_value_is_single_line() {
+    let definition = Definition {
+        key: "test_key".to_string(),
+        value: "\nSome value with multiple\n  newlines".to_string(),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        definition.get_preview_value(),
+        "Some value with multiple newlines"
+    );
+}
+
+#[test]
+fn preview_value_is_truncated() {
+    let definition = Definition {
+        key: "test_key".to_string(),
+        value: "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor"
+            .to_string(),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        definition.get_preview_value(),
+        "Lorem ipsum dolor sit amet, consectetur adipiscing elit, se…"
+    );
+    assert_eq!(definition.get_preview_value().chars().count(), 60);
+}
@@ -0,0 +1,241 @@
+use super::*;
+
+use regex::Regex;
+use std::path::PathBuf;
+use tower_lsp::lsp_types::{Location, Position, Range, Url};
+
+use crate::namespace_helper::NamespacedKey;
+use crate::DefinitionSource;
+
+fn create_definition(key: &str) -> Definition {
+    Definition {
+        key: key.to_string(),
+        value: "value".to_string(),
+        file: Some(DefinitionSource {
+            path: PathBuf::from("/en.json"),
+            extra_data: Default::default(),
+        }),
+        key_range: Some(Range::new(Position::new(1, 2), Position::new(1, 8))),
+        ..Default::default()
+    }
+}
+
+fn create_usage(key: &str) -> Usage {
+    Usage {
+        key: NamespacedKey::without_namespace(key),
+        location: Location::new(Url::parse("file:///index.js").unwrap(), Range::default()),
+    }
+}
+
+#[test]
+fn reports_unused_keys() {
+    let diagnostics = get_unused_key_diagnostics(
+        &[create_definition("used"), create_definition("unused")],
+        &[create_usage("used")],
+        &Default::default(),
+    );
+
+    assert_eq!(diagnostics.len(), 1);
+
+    let (path, diagnostic) = &diagnostics[0];
+    assert_eq!(path, &PathBuf::from("/en.json"));
+    assert_eq!(
+        diagnostic.message,
+        "`unused` is not used in the source files"
+    );
+    assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::HINT));
+    assert_eq!(diagnostic.tags, Some(vec![DiagnosticTag::UNNECESSARY]));
+}
+
+#[test]
+fn allows_dynamically_used_keys() {
+    let diagnostics = get_unused_key_diagnostics(
+        &[create_definition("status.active")],
+        &[],
+        &UnusedKeysConfig {
+            allow: vec![Regex::new("^status\\.").unwrap()],
+        },
+    );
+
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn reports_duplicate_keys_within_a_file() {
+    let diagnostics = get_duplicate_key_diagnostics(&[
+        create_definition("title"),
+        create_definition("title"),
+        create_definition("other"),
+    ]);
+
+    assert_eq!(diagnostics.len(), 2);
+
+    let (_, diagnostic) = &diagnostics[0];
+    assert_eq!(diagnostic.message, "`title` is defined 2 times");
+    assert_eq!(diagnostic.related_information.as_ref().unwrap().len(), 1);
+}
+
+#[test]
+fn ignores_same_keys_in_files_without_language() {
+    let mut other_file_definition = create_definition("title");
+    other_file_definition.file.as_mut().unwrap().path = PathBuf::from("/nl.json");
+
+    let diagnostics =
+        get_duplicate_key_diagnostics(&[create_definition("title"), other_file_definition]);
+
+    assert!(diagnostics.is_empty());
+}
+
+fn create_coded_diagnostic(line: u32, code: &str) -> Diagnostic {
+    Diagnostic {
+        range: Range::new(Position::new(line, 0), Position::new(line, 4)),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(code.to_string())),
+        ..Default::default()
+    }
+}
+
+fn create_diagnostics_config(config: serde_json::Value) -> DiagnosticsConfig {
+    serde_json::from_value(config).unwrap()
+}
+
+#[test]
+fn configures_severities_per_file() {
+    let config = create_diagnostics_config(serde_json::json!({
+        "rules": { "key-naming": "error", "unused-key": "off" },
+        "overrides": [{ "files": ["legacy/*.json"], "rules": { "key-naming": "info" } }]
+    }));
+
+    let diagnostics = vec![
+        create_coded_diagnostic(0, "key-naming"),
+        create_coded_diagnostic(1, "unused-key"),
+        create_coded_diagnostic(2, "duplicate-key"),
+    ];
+
+    let severities = |path: &str| {
+        configure_diagnostics(
+            diagnostics.clone(),
+            Some(&PathBuf::from(path)),
+            None,
+            &config,
+        )
+        .into_iter()
+        .map(|diagnostic| diagnostic.severity)
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        severities("/project/en.json"),
+        vec![
+            Some(DiagnosticSeverity::ERROR),
+            Some(DiagnosticSeverity::WARNING)
+        ]
+    );
+    assert_eq!(
+        severities("/project/legacy/en.json"),
+        vec![
+            Some(DiagnosticSeverity::INFORMATION),
+            Some(DiagnosticSeverity::WARNING)
+        ]
+    );
+}
+
+#[test]
+fn suppresses_diagnostics_with_comments() {
+    let text = "# lsp-translations-ignore-next-line\n\
+        first: value\n\
+        # lsp-translations-ignore-next-line unused-key\n\
+        second: value\n";
+
+    let diagnostics = configure_diagnostics(
+        vec![
+            create_coded_diagnostic(1, "key-naming"),
+            create_coded_diagnostic(3, "unused-key"),
+            create_coded_diagnostic(3, "key-naming"),
+        ],
+        None,
+        Some(text),
+        &Default::default(),
+    );
+
+    assert_eq!(diagnostics, vec![create_coded_diagnostic(3, "key-naming")]);
+}
+
+#[test]
+fn ignores_text_after_suppressed_codes() {
+    let text = "// lsp-translations-ignore-next-line unused-key, max-length -- keys are loaded at runtime\n\
+        translate('first')\n\
+        /* lsp-translations-ignore-next-line key-naming because of the legacy api */\n\
+        translate('second')\n";
+
+    let diagnostics = configure_diagnostics(
+        vec![
+            create_coded_diagnostic(1, "unused-key"),
+            create_coded_diagnostic(1, "max-length"),
+            create_coded_diagnostic(1, "key-naming"),
+            create_coded_diagnostic(3, "key-naming"),
+            create_coded_diagnostic(3, "unused-key"),
+        ],
+        None,
+        Some(text),
+        &Default::default(),
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            create_coded_diagnostic(1, "key-naming"),
+            create_coded_diagnostic(3, "unused-key"),
+        ]
+    );
+}
+
+#[test]
+fn keeps_rules_disabled_by_default_off() {
+    let diagnostics = vec![create_missing_key_diagnostic(Range::default(), "unknown")];
+
+    assert!(configure_diagnostics(diagnostics.clone(), None, None, &Default::default()).is_empty());
+    assert_eq!(
+        configure_diagnostics(
+            diagnostics,
+            None,
+            None,
+            &create_diagnostics_config(serde_json::json!({ "rules": { "missing-key": "hint" } }))
+        )[0]
+        .severity,
+        Some(DiagnosticSeverity::HINT)
+    );
+}
+
+#[test]
+fn points_icu_syntax_errors_at_the_broken_argument() {
+    let create_icu_definition = |value: &str, range: Range| Definition {
+        value: value.to_string(),
+        range: Some(range),
+        ..create_definition("items")
+    };
+    let get_ranges = |definition: Definition| {
+        get_icu_diagnostics(&[definition])
+            .into_iter()
+            .map(|(_, diagnostic)| diagnostic.range)
+            .collect::<Vec<Range>>()
+    };
+
+    let quoted = Range::new(Position::new(1, 11), Position::new(1, 35));
+    assert_eq!(
+        get_ranges(create_icu_definition("{count} {count, plural", quoted)),
+        vec![Range::new(Position::new(1, 20), Position::new(1, 21))]
+    );
+
+    let unquoted = Range::new(Position::new(1, 9), Position::new(1, 19));
+    assert_eq!(
+        get_ranges(create_icu_definition("Hé {name}}", unquoted)),
+        vec![Range::new(Position::new(1, 18), Position::new(1, 19))]
+    );
+
+    let escaped = Range::new(Position::new(1, 11), Position::new(1, 23));
+    assert_eq!(
+        get_ranges(create_icu_definition("\"{count\"", escaped)),
+        vec![escaped]
+    );
+}
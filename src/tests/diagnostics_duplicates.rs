@@ -0,0 +1,114 @@
+use itertools::Itertools;
+use serde_json::json;
+use std::env;
+use tower_lsp::{jsonrpc::Response, lsp_types::Url};
+
+mod helpers;
+use helpers::*;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+lazy_static! {
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/duplicates/*.json",
+                "./fixtures/duplicates/*.yml"
+            ]
+        },
+        "fileName": {
+            "details": "(?P<language>.+?)\\."
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+}
+
+fn get_fixture_uri(file_name: &str) -> Url {
+    Url::from_file_path(
+        env::current_dir()
+            .unwrap()
+            .join("src/tests/fixtures/duplicates")
+            .join(file_name),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn reports_duplicate_keys() {
+    let (_, _, diagnostics) =
+        prepare_with_workspace_config_and_diagnostics(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let get_diagnostics = |uri: Url| {
+        diagnostics
+            .iter()
+            .rev()
+            .find(|params| params["uri"] == json!(uri))
+            .unwrap()["diagnostics"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic["range"]["start"]["line"].as_u64().unwrap(),
+                    diagnostic["message"].as_str().unwrap().to_string(),
+                    diagnostic["relatedInformation"]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .map(|information| {
+                            (
+                                information["location"]["uri"].as_str().unwrap().to_string(),
+                                information["location"]["range"]["start"]["line"]
+                                    .as_u64()
+                                    .unwrap(),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .sorted()
+            .collect::<Vec<_>>()
+    };
+
+    let en_json = get_fixture_uri("en.json").to_string();
+    let en_yml = get_fixture_uri("en.yml").to_string();
+
+    assert_eq!(
+        get_diagnostics(get_fixture_uri("en.json")),
+        vec![
+            (
+                1,
+                "`title` is defined 2 times for `en`".to_string(),
+                vec![(en_json.clone(), 3)]
+            ),
+            (
+                2,
+                "`save` is defined 2 times for `en`".to_string(),
+                vec![(en_yml.clone(), 0)]
+            ),
+            (
+                3,
+                "`title` is defined 2 times for `en`".to_string(),
+                vec![(en_json.clone(), 1)]
+            ),
+        ]
+    );
+    assert_eq!(
+        get_diagnostics(get_fixture_uri("en.yml")),
+        vec![(
+            0,
+            "`save` is defined 2 times for `en`".to_string(),
+            vec![(en_json, 2)]
+        )]
+    );
+    assert_eq!(get_diagnostics(get_fixture_uri("nl.json")), vec![]);
+}
@@ -0,0 +1,128 @@
+use serde_json::json;
+use std::env;
+use tower_lsp::{jsonrpc::{Request, Response}, lsp_types::Url};
+
+mod helpers;
+use helpers::*;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+lazy_static! {
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/icu/*.json"
+            ]
+        },
+        "fileName": {
+            "details": "(?P<language>.+?)\\."
+        },
+        "messageFormat": "icu",
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+    static ref DID_OPEN_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/didOpen",
+            "params":{
+                "textDocument": {
+                    "uri": "file:///somefile.js",
+                    "languageId": "javascript",
+                    "version": 1,
+                    "text": "translate('items')"
+                }
+            }
+        }"#
+    )
+    .unwrap();
+    static ref HOVER_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/hover",
+            "params":{
+                "textDocument": {
+                    "uri": "file:///somefile.js"
+                },
+                "position": {
+                    "line": 0,
+                    "character": 13
+                }
+            },
+            "id": 2
+        }"#
+    )
+    .unwrap();
+}
+
+fn get_fixture_uri(file_name: &str) -> Url {
+    Url::from_file_path(
+        env::current_dir()
+            .unwrap()
+            .join("src/tests/fixtures/icu")
+            .join(file_name),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn reports_icu_problems() {
+    let (_, _, diagnostics) =
+        prepare_with_workspace_config_and_diagnostics(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let get_diagnostics = |uri: Url| {
+        let mut diagnostics = diagnostics
+            .iter()
+            .rev()
+            .find(|params| params["uri"] == json!(uri))
+            .unwrap()["diagnostics"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic["range"]["start"]["line"].as_u64().unwrap(),
+                    diagnostic["range"]["start"]["character"].as_u64().unwrap(),
+                    diagnostic["severity"].as_u64().unwrap(),
+                    diagnostic["message"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        diagnostics.sort();
+        diagnostics
+    };
+
+    assert_eq!(
+        get_diagnostics(get_fixture_uri("en.json")),
+        vec![
+            (2, 13, 1, "Unclosed `{`".to_string()),
+            (3, 10, 2, "`few` is not a plural category of `en`".to_string())
+        ]
+    );
+    assert_eq!(get_diagnostics(get_fixture_uri("ru.json")), vec![]);
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn hover_lists_icu_options() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    assert_eq!(service.call(DID_OPEN_REQUEST.clone()).await, Ok(None));
+
+    let result = service.call(HOVER_REQUEST.clone()).await.unwrap().unwrap();
+
+    let contents = serde_json::to_value(result).unwrap()["result"]["contents"].clone();
+    let contents = contents.as_str().unwrap();
+
+    assert!(contents.contains("|[count: **one** # item · **other** # items]"));
+    assert!(contents.contains("**few** # предмета"));
+}
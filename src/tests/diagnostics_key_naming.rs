@@ -0,0 +1,245 @@
+use serde_json::{json, Value};
+use std::env;
+use tower_lsp::{
+    jsonrpc::{Request, Response},
+    lsp_types::Url,
+};
+
+mod helpers;
+use helpers::*;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+lazy_static! {
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/key_naming/*.json"
+            ]
+        },
+        "fileName": {
+            "details": "(?P<language>.+?)\\."
+        },
+        "diagnostics": {
+            "keyNaming": {
+                "segment": "^[a-z0-9]+(_[a-z0-9]+)*$",
+                "maxDepth": 4,
+                "forbiddenCharacters": " "
+            }
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+    static ref EN_URI: Url = Url::from_file_path(
+        env::current_dir()
+            .unwrap()
+            .join("src/tests/fixtures/key_naming/en.json"),
+    )
+    .unwrap();
+    static ref NL_URI: Url = Url::from_file_path(
+        env::current_dir()
+            .unwrap()
+            .join("src/tests/fixtures/key_naming/nl.json"),
+    )
+    .unwrap();
+}
+
+fn code_action_request(uri: &str, diagnostics: Value) -> Request {
+    serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/codeAction",
+        "params": {
+            "textDocument": {
+                "uri": uri
+            },
+            "range": diagnostics[0]["range"],
+            "context": {
+                "diagnostics": diagnostics
+            }
+        },
+        "id": 2
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn reports_and_renames_keys_in_translation_files() {
+    let (mut service, _, diagnostics) =
+        prepare_with_workspace_config_and_diagnostics(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let en_diagnostics = diagnostics
+        .iter()
+        .rev()
+        .find(|params| params["uri"] == json!(*EN_URI))
+        .unwrap()["diagnostics"]
+        .clone();
+
+    assert_eq!(
+        en_diagnostics
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|diagnostic| (
+                diagnostic["range"]["start"]["line"].as_u64().unwrap(),
+                diagnostic["message"].as_str().unwrap()
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                2,
+                "`buttons.saveButton` segment `saveButton` does not match `^[a-z0-9]+(_[a-z0-9]+)*$`"
+            ),
+            (
+                5,
+                "`one.two.three.four.five` has 5 segments where at most 4 are allowed"
+            )
+        ]
+    );
+
+    let result = service
+        .call(code_action_request(
+            EN_URI.as_str(),
+            json!([en_diagnostics[0]]),
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+
+    let actions = serde_json::to_value(result).unwrap()["result"].clone();
+
+    assert_eq!(
+        actions[0]["title"],
+        "Rename `buttons.saveButton` to `buttons.save_button` in all files"
+    );
+    assert_eq!(
+        actions[0]["edit"]["changes"],
+        json!({
+            EN_URI.as_str(): [{
+                "range": {
+                    "start": { "line": 2, "character": 4 },
+                    "end": { "line": 2, "character": 16 }
+                },
+                "newText": "\"save_button\""
+            }],
+            NL_URI.as_str(): [{
+                "range": {
+                    "start": { "line": 2, "character": 4 },
+                    "end": { "line": 2, "character": 16 }
+                },
+                "newText": "\"save_button\""
+            }]
+        })
+    );
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn does_not_rename_keys_when_a_parent_must_be_renamed() {
+    let (mut service, _, diagnostics) =
+        prepare_with_workspace_config_and_diagnostics(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let nl_diagnostics = diagnostics
+        .iter()
+        .rev()
+        .find(|params| params["uri"] == json!(*NL_URI))
+        .unwrap()["diagnostics"]
+        .clone();
+    let menu_diagnostic = nl_diagnostics
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|diagnostic| diagnostic["data"]["key"] == "Menu.open")
+        .unwrap()
+        .clone();
+
+    let result = service
+        .call(code_action_request(
+            NL_URI.as_str(),
+            json!([menu_diagnostic]),
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(serde_json::to_value(result).unwrap()["result"], json!([]));
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn reports_and_renames_keys_in_source_files() {
+    let (mut service, mut messages) =
+        prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let diagnostics = call_and_wait_for_diagnostics(
+        &mut service,
+        &mut messages,
+        serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": {
+                "textDocument": {
+                    "uri": "file:///somefile.js",
+                    "languageId": "javascript",
+                    "version": 1,
+                    "text": "translate('buttons.saveButton')"
+                }
+            }
+        }))
+        .unwrap(),
+        "file:///somefile.js",
+    )
+    .await;
+
+    assert_eq!(
+        diagnostics,
+        json!([{
+            "range": {
+                "start": { "line": 0, "character": 11 },
+                "end": { "line": 0, "character": 29 }
+            },
+            "severity": 2,
+            "code": "key-naming",
+            "source": "lsp-translations",
+            "message": "`buttons.saveButton` segment `saveButton` does not match `^[a-z0-9]+(_[a-z0-9]+)*$`",
+            "data": { "namespace": null, "key": "buttons.saveButton" }
+        }])
+    );
+
+    let result = service
+        .call(code_action_request("file:///somefile.js", diagnostics))
+        .await
+        .unwrap()
+        .unwrap();
+
+    let actions = serde_json::to_value(result).unwrap()["result"].clone();
+    let key_edit = json!([{
+        "range": {
+            "start": { "line": 2, "character": 4 },
+            "end": { "line": 2, "character": 16 }
+        },
+        "newText": "\"save_button\""
+    }]);
+
+    assert_eq!(
+        actions[0]["edit"]["changes"],
+        json!({
+            EN_URI.as_str(): key_edit,
+            NL_URI.as_str(): key_edit,
+            "file:///somefile.js": [{
+                "range": {
+                    "start": { "line": 0, "character": 11 },
+                    "end": { "line": 0, "character": 29 }
+                },
+                "newText": "buttons.save_button"
+            }]
+        })
+    );
+}
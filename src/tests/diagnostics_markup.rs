@@ -0,0 +1,202 @@
+use serde_json::json;
+use std::env;
+use tower_lsp::{jsonrpc::Response, lsp_types::Url};
+
+mod helpers;
+use helpers::*;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+lazy_static! {
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/markup/*.json"
+            ]
+        },
+        "fileName": {
+            "details": "(?P<language>.+?)\\."
+        },
+        "diagnostics": {
+            "referenceLanguage": "en",
+            "rules": {
+                "unbalanced-tag": "warning",
+                "tag-mismatch": "warning"
+            }
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+    static ref UNBALANCED_TAG_WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/markup/*.json"
+            ]
+        },
+        "fileName": {
+            "details": "(?P<language>.+?)\\."
+        },
+        "diagnostics": {
+            "referenceLanguage": "en",
+            "rules": {
+                "unbalanced-tag": "error",
+                "tag-mismatch": "off"
+            }
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+    static ref DEFAULT_WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/markup/*.json"
+            ]
+        },
+        "fileName": {
+            "details": "(?P<language>.+?)\\."
+        },
+        "diagnostics": {
+            "referenceLanguage": "en"
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+}
+
+fn get_fixture_uri(file_name: &str) -> Url {
+    Url::from_file_path(
+        env::current_dir()
+            .unwrap()
+            .join("src/tests/fixtures/markup")
+            .join(file_name),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn reports_tag_problems() {
+    let (_, _, diagnostics) =
+        prepare_with_workspace_config_and_diagnostics(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let get_diagnostics = |uri: Url| {
+        let mut diagnostics = diagnostics
+            .iter()
+            .rev()
+            .find(|params| params["uri"] == json!(uri))
+            .unwrap()["diagnostics"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic["range"]["start"]["line"].as_u64().unwrap(),
+                    diagnostic["message"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        diagnostics.sort();
+        diagnostics
+    };
+
+    assert_eq!(
+        get_diagnostics(get_fixture_uri("nl.json")),
+        vec![
+            (
+                1,
+                "Tags differ from `en`: missing `<a>`, `</a>`".to_string()
+            ),
+            (2, "Tags differ from `en`: missing `</0>`".to_string()),
+            (2, "Unbalanced tags: `<0>` is not closed".to_string())
+        ]
+    );
+    assert_eq!(get_diagnostics(get_fixture_uri("en.json")), vec![]);
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn configures_tag_rules() {
+    let (_, _, diagnostics) = prepare_with_workspace_config_and_diagnostics(
+        &UNBALANCED_TAG_WORKSPACE_CONFIGURATION_REQUEST,
+    )
+    .await;
+
+    let nl_diagnostics = diagnostics
+        .iter()
+        .rev()
+        .find(|params| params["uri"] == json!(get_fixture_uri("nl.json")))
+        .unwrap()["diagnostics"]
+        .clone();
+
+    assert_eq!(
+        nl_diagnostics
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|diagnostic| (
+                diagnostic["range"]["start"]["line"].as_u64().unwrap(),
+                diagnostic["severity"].as_u64().unwrap(),
+                diagnostic["code"].as_str().unwrap()
+            ))
+            .collect::<Vec<_>>(),
+        vec![(2, 1, "unbalanced-tag")]
+    );
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn reports_tag_problems_as_warnings_by_default() {
+    let (_, _, diagnostics) =
+        prepare_with_workspace_config_and_diagnostics(&DEFAULT_WORKSPACE_CONFIGURATION_REQUEST)
+            .await;
+
+    let nl_diagnostics = diagnostics
+        .iter()
+        .rev()
+        .find(|params| params["uri"] == json!(get_fixture_uri("nl.json")))
+        .unwrap()["diagnostics"]
+        .clone();
+
+    let mut nl_diagnostics = nl_diagnostics
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|diagnostic| {
+            (
+                diagnostic["range"]["start"]["line"].as_u64().unwrap(),
+                diagnostic["severity"].as_u64().unwrap(),
+                diagnostic["code"].as_str().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    nl_diagnostics.sort();
+
+    assert_eq!(
+        nl_diagnostics,
+        vec![
+            (1, 2, "tag-mismatch"),
+            (2, 2, "tag-mismatch"),
+            (2, 2, "unbalanced-tag")
+        ]
+    );
+}
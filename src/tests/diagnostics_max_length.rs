@@ -0,0 +1,170 @@
+use itertools::Itertools;
+use serde_json::json;
+use std::env;
+use tower_lsp::{
+    jsonrpc::{Request, Response},
+    lsp_types::Url,
+};
+
+mod helpers;
+use helpers::*;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+lazy_static! {
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/max_length/*.arb"
+            ]
+        },
+        "fileName": {
+            "details": "(?P<language>.+?)\\."
+        },
+        "diagnostics": {
+            "maxLength": [
+                { "key": "^welcome$", "language": "de", "max": 24 }
+            ]
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+    static ref DID_OPEN_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/didOpen",
+            "params":{
+                "textDocument": {
+                    "uri": "file:///somefile.js",
+                    "languageId": "javascript",
+                    "version": 1,
+                    "text": "translate('checkout')"
+                }
+            }
+        }"#
+    )
+    .unwrap();
+    static ref HOVER_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/hover",
+            "params":{
+                "textDocument": {
+                    "uri": "file:///somefile.js"
+                },
+                "position": {
+                    "line": 0,
+                    "character": 13
+                }
+            },
+            "id": 2
+        }"#
+    )
+    .unwrap();
+}
+
+fn get_fixture_uri(file_name: &str) -> Url {
+    Url::from_file_path(
+        env::current_dir()
+            .unwrap()
+            .join("src/tests/fixtures/max_length")
+            .join(file_name),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn reports_too_long_translations() {
+    let (_, _, diagnostics) =
+        prepare_with_workspace_config_and_diagnostics(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let get_diagnostics = |uri: Url| {
+        diagnostics
+            .iter()
+            .rev()
+            .find(|params| params["uri"] == json!(uri))
+            .unwrap()["diagnostics"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic["range"]["start"]["line"].as_u64().unwrap(),
+                    diagnostic["message"].as_str().unwrap().to_string(),
+                )
+            })
+            .sorted()
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        get_diagnostics(get_fixture_uri("de.arb")),
+        vec![
+            (
+                2,
+                "`checkout` is 15 characters long, at most 12 are allowed".to_string()
+            ),
+            (
+                3,
+                "`welcome` is 30 characters long, at most 24 are allowed".to_string()
+            )
+        ]
+    );
+    assert_eq!(get_diagnostics(get_fixture_uri("en.arb")), vec![]);
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn hover_shows_length() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    assert_eq!(service.call(DID_OPEN_REQUEST.clone()).await, Ok(None));
+
+    let result = service.call(HOVER_REQUEST.clone()).await.unwrap().unwrap();
+
+    let contents = serde_json::to_value(result).unwrap()["result"]["contents"].clone();
+    let contents = contents.as_str().unwrap();
+
+    assert!(contents.contains("|Check out `9/12`"));
+    assert!(contents.contains("|Zur Kasse gehen ⚠️ `15/12`"));
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn arb_metadata_is_not_a_translation() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let result = service
+        .call(
+            serde_json::from_value(json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/codeLens",
+                "params": {
+                    "textDocument": { "uri": get_fixture_uri("en.arb") }
+                },
+                "id": 2
+            }))
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    let lines = serde_json::to_value(result).unwrap()["result"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|lens| lens["range"]["start"]["line"].as_u64().unwrap())
+        .sorted()
+        .collect::<Vec<u64>>();
+
+    assert_eq!(lines, vec![2, 6]);
+}
@@ -0,0 +1,125 @@
+use serde_json::json;
+use std::env;
+use tower_lsp::{
+    jsonrpc::{Request, Response},
+    lsp_types::Url,
+};
+
+mod helpers;
+use helpers::*;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+lazy_static! {
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/placeholders/*.json"
+            ]
+        },
+        "fileName": {
+            "details": "(?P<language>.+?)\\."
+        },
+        "diagnostics": {
+            "referenceLanguage": "en"
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+    static ref DID_OPEN_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/didOpen",
+            "params":{
+                "textDocument": {
+                    "uri": "file:///somefile.js",
+                    "languageId": "javascript",
+                    "version": 1,
+                    "text": "translate('greeting')"
+                }
+            }
+        }"#
+    )
+    .unwrap();
+    static ref HOVER_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/hover",
+            "params":{
+                "textDocument": {
+                    "uri": "file:///somefile.js"
+                },
+                "position": {
+                    "line": 0,
+                    "character": 13
+                }
+            },
+            "id": 2
+        }"#
+    )
+    .unwrap();
+}
+
+fn get_fixture_uri(file_name: &str) -> Url {
+    Url::from_file_path(
+        env::current_dir()
+            .unwrap()
+            .join("src/tests/fixtures/placeholders")
+            .join(file_name),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn reports_placeholder_mismatch() {
+    let (_, _, diagnostics) =
+        prepare_with_workspace_config_and_diagnostics(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let get_diagnostics = |uri: Url| {
+        diagnostics
+            .iter()
+            .rev()
+            .find(|params| params["uri"] == json!(uri))
+            .unwrap()["diagnostics"]
+            .clone()
+    };
+
+    assert_eq!(get_diagnostics(get_fixture_uri("en.json")), json!([]));
+    assert_eq!(
+        get_diagnostics(get_fixture_uri("nl.json")),
+        json!([{
+            "range": {
+                "start": { "line": 1, "character": 15 },
+                "end": { "line": 1, "character": 29 }
+            },
+            "severity": 2,
+            "code": "placeholder-mismatch",
+            "source": "lsp-translations",
+            "message": "Placeholders differ from `en`: missing `{{name}}`, unexpected `{{naam}}`"
+        }])
+    );
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn hover_shows_placeholder_mismatch() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    assert_eq!(service.call(DID_OPEN_REQUEST.clone()).await, Ok(None));
+
+    let result = service.call(HOVER_REQUEST.clone()).await.unwrap().unwrap();
+
+    let contents = serde_json::to_value(result).unwrap()["result"]["contents"].clone();
+    let contents = contents.as_str().unwrap();
+
+    assert!(contents.contains("Hallo {{naam}} ⚠️ *missing `{{name}}`, unexpected `{{naam}}`*"));
+    assert!(contents.contains("|Hello {{name}}|"));
+}
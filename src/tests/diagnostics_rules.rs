@@ -0,0 +1,124 @@
+use serde_json::json;
+use std::env;
+use tower_lsp::{jsonrpc::Response, lsp_types::Url};
+
+mod helpers;
+use helpers::*;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+lazy_static! {
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/rules/*.yml"
+            ]
+        },
+        "fileName": {
+            "details": "(?P<language>.+?)\\."
+        },
+        "diagnostics": {
+            "rules": {
+                "missing-language": "error",
+                "missing-key": "hint"
+            },
+            "overrides": [
+                {
+                    "files": ["rules/nl.yml"],
+                    "rules": { "missing-language": "off" }
+                }
+            ]
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+}
+
+fn get_fixture_uri(file_name: &str) -> Url {
+    Url::from_file_path(
+        env::current_dir()
+            .unwrap()
+            .join("src/tests/fixtures/rules")
+            .join(file_name),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn applies_rules_overrides_and_suppressions() {
+    let (_, _, diagnostics) =
+        prepare_with_workspace_config_and_diagnostics(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let get_diagnostics = |uri: Url| {
+        diagnostics
+            .iter()
+            .rev()
+            .find(|params| params["uri"] == json!(uri))
+            .unwrap()["diagnostics"]
+            .clone()
+    };
+
+    assert_eq!(
+        get_diagnostics(get_fixture_uri("en.yml")),
+        json!([{
+            "range": {
+                "start": { "line": 3, "character": 0 },
+                "end": { "line": 3, "character": 6 }
+            },
+            "severity": 1,
+            "code": "missing-language",
+            "source": "lsp-translations",
+            "message": "`footer` is missing in `nl`"
+        }])
+    );
+    assert_eq!(get_diagnostics(get_fixture_uri("nl.yml")), json!([]));
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn reports_missing_keys_in_source_files() {
+    let (mut service, mut messages) =
+        prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let diagnostics = call_and_wait_for_diagnostics(
+        &mut service,
+        &mut messages,
+        serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": {
+                "textDocument": {
+                    "uri": "file:///somefile.js",
+                    "languageId": "javascript",
+                    "version": 1,
+                    "text": "translate('title')\ntranslate('unknown')"
+                }
+            }
+        }))
+        .unwrap(),
+        "file:///somefile.js",
+    )
+    .await;
+
+    assert_eq!(
+        diagnostics,
+        json!([{
+            "range": {
+                "start": { "line": 1, "character": 11 },
+                "end": { "line": 1, "character": 18 }
+            },
+            "severity": 4,
+            "code": "missing-key",
+            "source": "lsp-translations",
+            "message": "`unknown` is not defined in any translation file"
+        }])
+    );
+}
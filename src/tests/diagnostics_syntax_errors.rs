@@ -0,0 +1,130 @@
+use serde_json::json;
+use std::env;
+use tower_lsp::{
+    jsonrpc::{Request, Response},
+    lsp_types::Url,
+};
+
+mod helpers;
+use helpers::*;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+lazy_static! {
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/syntax_errors/*.json"
+            ]
+        },
+        "fileName": {
+            "details": "(?P<language>.+?)\\."
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+    static ref DID_OPEN_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/didOpen",
+            "params":{
+                "textDocument": {
+                    "uri": "file:///somefile.js",
+                    "languageId": "javascript",
+                    "version": 1,
+                    "text": "translate('')"
+                }
+            }
+        }"#
+    )
+    .unwrap();
+    static ref COMPLETION_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/completion",
+            "params":{
+                "textDocument": {
+                    "uri": "file:///somefile.js"
+                },
+                "position": {
+                    "line": 0,
+                    "character": 11
+                },
+                "context": {
+                    "triggerKind": 1
+                }
+            },
+            "id": 2
+        }"#
+    )
+    .unwrap();
+}
+
+fn get_fixture_uri(file_name: &str) -> Url {
+    Url::from_file_path(
+        env::current_dir()
+            .unwrap()
+            .join("src/tests/fixtures/syntax_errors")
+            .join(file_name),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn reports_syntax_errors() {
+    let (_, _, diagnostics) =
+        prepare_with_workspace_config_and_diagnostics(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let params = diagnostics
+        .iter()
+        .rev()
+        .find(|params| params["uri"] == json!(get_fixture_uri("en.json")))
+        .unwrap();
+
+    assert_eq!(
+        params["diagnostics"],
+        json!([{
+            "range": {
+                "start": { "line": 1, "character": 2 },
+                "end": { "line": 2, "character": 20 }
+            },
+            "severity": 1,
+            "code": "syntax-error",
+            "source": "lsp-translations",
+            "message": "Syntax error near `\"broken\": \"Broken\"`"
+        }])
+    );
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn keeps_valid_definitions() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    assert_eq!(service.call(DID_OPEN_REQUEST.clone()).await, Ok(None));
+
+    let result = service
+        .call(COMPLETION_REQUEST.clone())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let mut labels = serde_json::to_value(result).unwrap()["result"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["label"].as_str().unwrap().to_string())
+        .collect::<Vec<String>>();
+
+    labels.sort();
+
+    assert_eq!(labels, vec!["after", "broken", "valid"]);
+}
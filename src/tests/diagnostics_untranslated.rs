@@ -0,0 +1,86 @@
+use serde_json::json;
+use std::env;
+use tower_lsp::{jsonrpc::Response, lsp_types::Url};
+
+mod helpers;
+use helpers::*;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+lazy_static! {
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/untranslated/*.json"
+            ]
+        },
+        "fileName": {
+            "details": "(?P<language>.+?)\\."
+        },
+        "diagnostics": {
+            "referenceLanguage": "en",
+            "untranslated": {
+                "allow": ["^brand$"]
+            }
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+}
+
+fn get_fixture_uri(file_name: &str) -> Url {
+    Url::from_file_path(
+        env::current_dir()
+            .unwrap()
+            .join("src/tests/fixtures/untranslated")
+            .join(file_name),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn reports_untranslated_values() {
+    let (_, _, diagnostics) =
+        prepare_with_workspace_config_and_diagnostics(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let get_diagnostics = |uri: Url| {
+        let mut diagnostics = diagnostics
+            .iter()
+            .rev()
+            .find(|params| params["uri"] == json!(uri))
+            .unwrap()["diagnostics"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic["range"]["start"]["line"].as_u64().unwrap(),
+                    diagnostic["message"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        diagnostics.sort();
+        diagnostics
+    };
+
+    assert_eq!(
+        get_diagnostics(get_fixture_uri("de.json")),
+        vec![
+            (
+                1,
+                "`title` is probably untranslated, the value is the same as in `en`".to_string()
+            ),
+            (4, "`save` is probably untranslated".to_string())
+        ]
+    );
+    assert_eq!(get_diagnostics(get_fixture_uri("en.json")), vec![]);
+}
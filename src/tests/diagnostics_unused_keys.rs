@@ -0,0 +1,362 @@
+use futures::{join, StreamExt};
+use serde_json::{json, Value};
+use std::env;
+use tower_lsp::{
+    jsonrpc::{Request, Response},
+    lsp_types::Url,
+    ClientSocket,
+};
+
+mod helpers;
+use helpers::*;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+lazy_static! {
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/unused_keys/*.json"
+            ]
+        },
+        "sourceFiles": {
+            "include": [
+                "./fixtures/unused_keys/*.js"
+            ]
+        },
+        "fileName": {
+            "details": "(?P<language>.+?)\\."
+        },
+        "diagnostics": {
+            "unusedKeys": {
+                "allow": ["^dynamic\\."]
+            }
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+    static ref ANCHORS_WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/unused_keys_anchors/*.yml"
+            ]
+        },
+        "sourceFiles": {
+            "include": [
+                "./fixtures/unused_keys_anchors/*.js"
+            ]
+        },
+        "fileName": {
+            "details": "(?P<language>.+?)\\."
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+    static ref EN_URI: Url = get_fixture_uri("en.json");
+    static ref NL_URI: Url = get_fixture_uri("nl.json");
+}
+
+fn get_fixture_uri(file_name: &str) -> Url {
+    Url::from_file_path(
+        env::current_dir()
+            .unwrap()
+            .join("src/tests/fixtures/unused_keys")
+            .join(file_name),
+    )
+    .unwrap()
+}
+
+fn get_diagnostics_for_uri(diagnostics: &[Value], uri: &Url) -> Value {
+    diagnostics
+        .iter()
+        .rev()
+        .find(|params| params["uri"] == json!(uri))
+        .unwrap()["diagnostics"]
+        .clone()
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn reports_unused_keys() {
+    let (_, _, diagnostics) =
+        prepare_with_workspace_config_and_diagnostics(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let en_diagnostics = get_diagnostics_for_uri(&diagnostics, &EN_URI);
+
+    assert_eq!(
+        en_diagnostics,
+        json!([{
+            "range": {
+                "start": { "line": 2, "character": 2 },
+                "end": { "line": 2, "character": 10 }
+            },
+            "severity": 4,
+            "code": "unused-key",
+            "source": "lsp-translations",
+            "message": "`unused` is not used in the source files",
+            "tags": [1],
+            "data": { "namespace": null, "key": "unused" }
+        }])
+    );
+
+    assert_eq!(
+        get_diagnostics_for_uri(&diagnostics, &NL_URI)
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn removes_unused_key_from_all_files() {
+    let (mut service, _, diagnostics) =
+        prepare_with_workspace_config_and_diagnostics(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let code_action_request: Request = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/codeAction",
+        "params": {
+            "textDocument": {
+                "uri": *EN_URI
+            },
+            "range": {
+                "start": { "line": 2, "character": 2 },
+                "end": { "line": 2, "character": 2 }
+            },
+            "context": {
+                "diagnostics": get_diagnostics_for_uri(&diagnostics, &EN_URI)
+            }
+        },
+        "id": 2
+    }))
+    .unwrap();
+
+    let result = service.call(code_action_request).await.unwrap().unwrap();
+    let actions = serde_json::to_value(result).unwrap()["result"].clone();
+
+    assert_eq!(
+        actions[0]["title"],
+        "Remove `unused` from all translation files"
+    );
+    assert_eq!(
+        actions[0]["edit"]["changes"][EN_URI.as_str()],
+        json!([{
+            "range": {
+                "start": { "line": 2, "character": 0 },
+                "end": { "line": 3, "character": 0 }
+            },
+            "newText": ""
+        }])
+    );
+    assert_eq!(
+        actions[0]["edit"]["changes"][NL_URI.as_str()],
+        json!([{
+            "range": {
+                "start": { "line": 1, "character": 20 },
+                "end": { "line": 2, "character": 24 }
+            },
+            "newText": ""
+        }])
+    );
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn keys_merged_by_yaml_aliases_are_used_through_their_copies() {
+    let (_, _, diagnostics) =
+        prepare_with_workspace_config_and_diagnostics(&ANCHORS_WORKSPACE_CONFIGURATION_REQUEST)
+            .await;
+
+    let uri = Url::from_file_path(
+        env::current_dir()
+            .unwrap()
+            .join("src/tests/fixtures/unused_keys_anchors/en.yml"),
+    )
+    .unwrap();
+
+    let reported = get_diagnostics_for_uri(&diagnostics, &uri)
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|diagnostic| {
+            (
+                diagnostic["range"]["start"]["line"].as_u64().unwrap(),
+                diagnostic["data"]["key"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect::<Vec<(u64, String)>>();
+
+    // `defaults.title` is used as `page.title` and nothing is reported on the merge key
+    assert_eq!(
+        reported,
+        vec![
+            (2, "defaults.body".to_string()),
+            (5, "page.body".to_string())
+        ]
+    );
+}
+
+/// Returns the diagnostics published for `uri` and the files published before them
+async fn wait_for_diagnostics(messages: &mut ClientSocket, uri: &str) -> (Vec<String>, Value) {
+    let mut published_uris = vec![];
+
+    while let Some(request) = messages.next().await {
+        let value = serde_json::to_value(request).unwrap();
+        if value["method"] != "textDocument/publishDiagnostics" {
+            continue;
+        }
+
+        if value["params"]["uri"] == uri {
+            return (published_uris, value["params"]["diagnostics"].clone());
+        }
+        published_uris.push(value["params"]["uri"].as_str().unwrap().to_string());
+    }
+
+    panic!("No diagnostics published for {}", uri);
+}
+
+fn did_change_request(text: &str) -> Request {
+    serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didChange",
+        "params": {
+            "textDocument": { "uri": "file:///edited.js", "version": 2 },
+            "contentChanges": [{ "text": text }]
+        }
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+#[timeout(1500)]
+async fn changes_only_recheck_translation_files_when_used_keys_change() {
+    let (mut service, mut messages, _) =
+        prepare_with_workspace_config_and_diagnostics(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let did_open_request: Request = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didOpen",
+        "params": {
+            "textDocument": {
+                "uri": "file:///edited.js",
+                "languageId": "javascript",
+                "version": 1,
+                "text": "translate('used');"
+            }
+        }
+    }))
+    .unwrap();
+    let (result, _) = join!(
+        service.call(did_open_request),
+        wait_for_diagnostics(&mut messages, "file:///edited.js")
+    );
+    assert_eq!(result, Ok(None));
+
+    // Using `unused` clears its hint in the translation files
+    assert_eq!(
+        service
+            .call(did_change_request(
+                "translate('used');\ntranslate('unused');"
+            ))
+            .await,
+        Ok(None)
+    );
+    let (_, en_diagnostics) = wait_for_diagnostics(&mut messages, EN_URI.as_str()).await;
+    assert_eq!(en_diagnostics, json!([]));
+    wait_for_diagnostics(&mut messages, "file:///edited.js").await;
+
+    // Edits that keep the same keys only recheck the document itself
+    assert_eq!(
+        service
+            .call(did_change_request(
+                "translate('used');\ntranslate('unused');\n"
+            ))
+            .await,
+        Ok(None)
+    );
+    let (published_uris, _) = wait_for_diagnostics(&mut messages, "file:///edited.js").await;
+    assert_eq!(published_uris, Vec::<String>::new());
+}
+
+#[tokio::test]
+#[timeout(1500)]
+async fn closing_a_document_drops_its_usages() {
+    let (mut service, mut messages, _) =
+        prepare_with_workspace_config_and_diagnostics(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let did_open_request: Request = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didOpen",
+        "params": {
+            "textDocument": {
+                "uri": "file:///edited.js",
+                "languageId": "javascript",
+                "version": 1,
+                "text": "translate('unused');"
+            }
+        }
+    }))
+    .unwrap();
+    let (result, _) = join!(
+        service.call(did_open_request),
+        wait_for_diagnostics(&mut messages, "file:///edited.js")
+    );
+    assert_eq!(result, Ok(None));
+
+    let did_close_request: Request = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didClose",
+        "params": {
+            "textDocument": { "uri": "file:///edited.js" }
+        }
+    }))
+    .unwrap();
+    assert_eq!(service.call(did_close_request).await, Ok(None));
+
+    // The document is not a source file, so `unused` is unused again
+    let (_, en_diagnostics) = wait_for_diagnostics(&mut messages, EN_URI.as_str()).await;
+    assert_eq!(en_diagnostics.as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+#[timeout(1500)]
+async fn opening_a_document_only_checks_the_document() {
+    let (mut service, mut messages, _) =
+        prepare_with_workspace_config_and_diagnostics(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let did_open_request: Request = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didOpen",
+        "params": {
+            "textDocument": {
+                "uri": "file:///opened.js",
+                "languageId": "javascript",
+                "version": 1,
+                "text": "const title = 'Title';"
+            }
+        }
+    }))
+    .unwrap();
+    let (result, (published_uris, _)) = join!(
+        service.call(did_open_request),
+        wait_for_diagnostics(&mut messages, "file:///opened.js")
+    );
+
+    assert_eq!(result, Ok(None));
+    assert_eq!(published_uris, Vec::<String>::new());
+}
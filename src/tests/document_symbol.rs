@@ -0,0 +1,116 @@
+use serde_json::{json, Value};
+use std::env;
+use tower_lsp::{
+    jsonrpc::{Request, Response},
+    lsp_types::Url,
+};
+
+mod helpers;
+use helpers::*;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+lazy_static! {
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/non_string_values/*.yml"
+            ]
+        },
+        "fileName": {
+            "details": ""
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+}
+
+fn document_symbol_request(path: &str) -> Request {
+    let uri = Url::from_file_path(env::current_dir().unwrap().join(path)).unwrap();
+
+    serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/documentSymbol",
+        "params": {
+            "textDocument": {
+                "uri": uri
+            }
+        },
+        "id": 2
+    }))
+    .unwrap()
+}
+
+/// Leaves out the ranges to keep the expected outline readable
+fn get_outline(symbols: &Value) -> Value {
+    Value::Array(
+        symbols
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|symbol| match symbol.get("children") {
+                Some(children) => json!([symbol["name"], get_outline(children)]),
+                None => json!([symbol["name"], symbol["detail"]]),
+            })
+            .collect(),
+    )
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn document_symbol_outline() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let result = service
+        .call(document_symbol_request(
+            "src/tests/fixtures/non_string_values/values.yml",
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+
+    let symbols = serde_json::to_value(result).unwrap()["result"].clone();
+
+    assert_eq!(
+        get_outline(&symbols),
+        json!([
+            ["enabled", "true"],
+            ["count", "3"],
+            ["missing", "~"],
+            ["title", "Title"],
+            ["list", [["[0]", "first"], ["[1]", [["[0]", "nested"]]]]],
+            ["items", [["[0]", [["name", "Item"]]]]]
+        ])
+    );
+
+    assert_eq!(
+        symbols[3]["selectionRange"],
+        json!({
+            "start": { "line": 3, "character": 0 },
+            "end": { "line": 3, "character": 5 }
+        })
+    );
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn document_symbol_ignores_other_files() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let result = service
+        .call(document_symbol_request(
+            "src/tests/fixtures/non_string_values/values.json",
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(serde_json::to_value(result).unwrap()["result"], Value::Null);
+}
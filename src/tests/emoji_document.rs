@@ -1,4 +1,4 @@
-use tower_lsp::jsonrpc::Incoming;
+use tower_lsp::jsonrpc::Request;
 
 mod helpers;
 use helpers::*;
@@ -7,7 +7,7 @@ use helpers::*;
 use pretty_assertions::assert_eq;
 
 lazy_static! {
-    static ref DID_OPEN_REQUEST: Incoming = serde_json::from_str(
+    static ref DID_OPEN_REQUEST: Request = serde_json::from_str(
         r#"{
             "jsonrpc":"2.0",
             "method":"textDocument/didOpen",
@@ -18,12 +18,11 @@ lazy_static! {
                     "version": 1,
                     "text": ""
                 }
-            },
-            "id": 2
+            }
         }"#
     )
     .unwrap();
-    static ref DID_CHANGE_ADD_TEXT_REQUEST: Incoming = serde_json::from_str(
+    static ref DID_CHANGE_ADD_TEXT_REQUEST: Request = serde_json::from_str(
         r#"{
             "jsonrpc":"2.0",
             "method":"textDocument/didChange",
@@ -48,12 +47,11 @@ lazy_static! {
                         "text": "test🇳🇱 "
                     }
                 ]
-            },
-            "id": 2
+            }
         }"#
     )
     .unwrap();
-    static ref DID_CHANGE_REMOVE_TEXT_REQUEST: Incoming = serde_json::from_str(
+    static ref DID_CHANGE_REMOVE_TEXT_REQUEST: Request = serde_json::from_str(
         r#"{
             "jsonrpc":"2.0",
             "method":"textDocument/didChange",
@@ -78,8 +76,7 @@ lazy_static! {
                         "text": ""
                     }
                 ]
-            },
-            "id": 3
+            }
         }"#
     )
     .unwrap();
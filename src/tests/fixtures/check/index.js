@@ -0,0 +1,2 @@
+translate('title');
+translate('missing');
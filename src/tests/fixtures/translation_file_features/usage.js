@@ -0,0 +1,3 @@
+translate('checkout.title');
+translate('checkout.title');
+translate('checkout.pay');
@@ -0,0 +1 @@
+translate('page.title');
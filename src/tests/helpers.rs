@@ -1,46 +1,53 @@
-use tower_lsp::MessageStream;
+use tower_lsp::ClientSocket;
 use tower_test::mock::Spawn;
 
-use tower_lsp::jsonrpc::{Incoming, Outgoing};
+use tower_lsp::jsonrpc::{Request, Response};
 use tower_lsp::LspService;
 
 use core::task::Poll;
 
 use crate::Backend;
 
-use futures::select;
-use futures::{FutureExt, StreamExt};
+use futures::{join, select};
+use futures::{FutureExt, SinkExt, StreamExt};
+use serde_json::Value;
 use std::env;
 
 #[cfg(test)]
 use pretty_assertions::assert_eq;
 
 lazy_static! {
-    static ref INITIALIZE_REQUEST: Incoming = serde_json::from_str(
+    static ref INITIALIZE_REQUEST: Request = serde_json::from_str(
         r#"{"jsonrpc":"2.0","method":"initialize","params":{"capabilities":{}},"id":1}"#
     )
     .unwrap();
 
 
-    static ref INITIALIZE_RESPONSE: Outgoing = Outgoing::Response(serde_json::from_str(r#"{
+    static ref INITIALIZE_RESPONSE: Response = serde_json::from_str(r#"{
             "jsonrpc":"2.0",
             "result": {
                 "capabilities": {
+                    "codeActionProvider": true,
+                    "codeLensProvider": {
+                        "resolveProvider": true
+                    },
                     "completionProvider": {
                         "resolveProvider": true,
                         "triggerCharacters": ["'", "\"", "`", "."]
                     },
+                    "documentSymbolProvider": true,
                     "hoverProvider": true,
                     "textDocumentSync": 2,
+                    "workspaceSymbolProvider": true,
                     "workspace": {"workspaceFolders": {"changeNotifications": true, "supported": true}}
                 }
             },
             "id":1
-        }"#).unwrap());
+        }"#).unwrap();
 
-    static ref INITIALIZED_REQUEST: Incoming = serde_json::from_str(r#"{"jsonrpc":"2.0","method":"initialized","params":{}}"#).unwrap();
+    static ref INITIALIZED_REQUEST: Request = serde_json::from_str(r#"{"jsonrpc":"2.0","method":"initialized","params":{}}"#).unwrap();
 
-    static ref WORKSPACE_CONFIGURATION_REQUEST: Incoming = serde_json::from_str(
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
         r#"{"jsonrpc":"2.0","result": [
     {
         "translationFiles": {
@@ -64,7 +71,7 @@ lazy_static! {
     )
     .unwrap();
 
-    static ref WORKSPACE_WORKSPACE_FOLDERS_REQUEST: Incoming = serde_json::from_str(
+    static ref WORKSPACE_WORKSPACE_FOLDERS_REQUEST: Response = serde_json::from_str(
         format!(
             r#"
             {{
@@ -90,7 +97,7 @@ lazy_static! {
     .unwrap();
 
 
-    static ref MESSAGE_OK_RESPONSE_CONFIG_CAPABILITY: Incoming = serde_json::from_str(
+    static ref MESSAGE_OK_RESPONSE_CONFIG_CAPABILITY: Response = serde_json::from_str(
         r#"
         {
             "jsonrpc": "2.0",
@@ -101,7 +108,7 @@ lazy_static! {
     .unwrap();
 
 
-    static ref MESSAGE_OK_RESPONSE_WATCH_CAPABILITY: Incoming = serde_json::from_str(
+    static ref MESSAGE_OK_RESPONSE_WATCH_CAPABILITY: Response = serde_json::from_str(
         r#"
         {
             "jsonrpc": "2.0",
@@ -113,47 +120,78 @@ lazy_static! {
 
 }
 
-pub fn init_service() -> (Spawn<LspService>, MessageStream) {
+pub fn init_service() -> (Spawn<LspService<Backend>>, ClientSocket) {
     let (service, messages) = LspService::new(Backend::new);
     (Spawn::new(service), messages)
 }
 
 pub async fn handle_lsp_message(
-    service: &mut Spawn<LspService>,
-    messages: &mut MessageStream,
-    responses: Vec<&Incoming>,
+    messages: &mut ClientSocket,
+    responses: Vec<&Response>,
+    diagnostics: &mut Vec<Value>,
 ) {
     let mut i = 0;
-    while let Some(message) = messages.next().await {
-        match message {
-            Outgoing::Response(_) => todo!(),
-            Outgoing::Request(req) => {
-                let value = serde_json::to_value(req.clone()).unwrap();
-                if value["method"] == "window/logMessage" {
-                    println!(
-                        "[window/logMessage] {:?}",
-                        value["params"]["message"].as_str().unwrap()
-                    );
-                } else {
-                    println!("[msg request] {:?}", &req);
-
-                    let result = service.call(responses[i].clone()).await;
-                    println!("[msg response #{}] {:?}", i, result);
-                    i += 1;
-                }
-            }
+    while let Some(req) = messages.next().await {
+        let value = serde_json::to_value(req.clone()).unwrap();
+        if value["method"] == "window/logMessage" {
+            println!(
+                "[window/logMessage] {:?}",
+                value["params"]["message"].as_str().unwrap()
+            );
+        } else if value["method"] == "textDocument/publishDiagnostics" {
+            diagnostics.push(value["params"].clone());
+        } else {
+            println!("[msg request] {:?}", &req);
+
+            let result = messages.send(responses[i].clone()).await;
+            println!("[msg response #{}] {:?}", i, result);
+            i += 1;
         }
     }
 }
 
-pub async fn prepare_workspace() -> (Spawn<LspService>, MessageStream) {
+/// Calls `request` while waiting for the diagnostics that are published for `uri`
+pub async fn call_and_wait_for_diagnostics(
+    service: &mut Spawn<LspService<Backend>>,
+    messages: &mut ClientSocket,
+    request: Request,
+    uri: &str,
+) -> Value {
+    let (result, diagnostics) = join!(service.call(request), async {
+        while let Some(req) = messages.next().await {
+            let value = serde_json::to_value(req).unwrap();
+            if value["method"] == "textDocument/publishDiagnostics" && value["params"]["uri"] == uri
+            {
+                return value["params"]["diagnostics"].clone();
+            }
+        }
+        Value::Null
+    });
+
+    assert!(result.is_ok());
+    diagnostics
+}
+
+pub async fn prepare_workspace() -> (Spawn<LspService<Backend>>, ClientSocket) {
     prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await
 }
 
 pub async fn prepare_with_workspace_config(
-    workspace_config_request: &Incoming,
-) -> (Spawn<LspService>, MessageStream) {
+    workspace_config_request: &Response,
+) -> (Spawn<LspService<Backend>>, ClientSocket) {
+    let (service, messages, _) =
+        prepare_with_workspace_config_and_diagnostics(workspace_config_request).await;
+
+    (service, messages)
+}
+
+/// Like `prepare_with_workspace_config`, but also returns the params of
+/// every `textDocument/publishDiagnostics` notification sent while initializing
+pub async fn prepare_with_workspace_config_and_diagnostics(
+    workspace_config_request: &Response,
+) -> (Spawn<LspService<Backend>>, ClientSocket, Vec<Value>) {
     let (mut service, mut messages) = init_service();
+    let mut diagnostics = vec![];
 
     assert_eq!(
         service.call(INITIALIZE_REQUEST.clone()).await,
@@ -167,7 +205,6 @@ pub async fn prepare_with_workspace_config(
             assert_eq!(req.unwrap(), None);
         },
         () = handle_lsp_message(
-            &mut service,
             &mut messages,
             vec![
                 &MESSAGE_OK_RESPONSE_CONFIG_CAPABILITY,
@@ -175,10 +212,19 @@ pub async fn prepare_with_workspace_config(
                 &WORKSPACE_WORKSPACE_FOLDERS_REQUEST,
                 &MESSAGE_OK_RESPONSE_WATCH_CAPABILITY
             ],
+            &mut diagnostics,
         ).fuse() => {
             panic!("lsp messages should not finish faster than finishing request")
         },
     );
 
-    (Spawn::new(service.into_inner()), messages)
+    // The last notifications may still be waiting in the stream
+    while let Some(Some(req)) = messages.next().now_or_never() {
+        let value = serde_json::to_value(req).unwrap();
+        if value["method"] == "textDocument/publishDiagnostics" {
+            diagnostics.push(value["params"].clone());
+        }
+    }
+
+    (Spawn::new(service.into_inner()), messages, diagnostics)
 }
@@ -1,4 +1,4 @@
-use tower_lsp::jsonrpc::{Incoming, Outgoing, Response};
+use tower_lsp::jsonrpc::{Request, Response};
 
 mod helpers;
 use helpers::*;
@@ -7,7 +7,7 @@ use helpers::*;
 use pretty_assertions::assert_eq;
 
 lazy_static! {
-    static ref DID_OPEN_REQUEST: Incoming = serde_json::from_str(
+    static ref DID_OPEN_REQUEST: Request = serde_json::from_str(
         r#"{
             "jsonrpc":"2.0",
             "method":"textDocument/didOpen",
@@ -18,13 +18,12 @@ lazy_static! {
                     "version": 1,
                     "text": "translate('main.header.title'); translate('some.unknown.translation')"
                 }
-            },
-            "id":1
+            }
         }"#
     )
     .unwrap();
 
-    static ref HOVER_REQUEST: Incoming = serde_json::from_str(
+    static ref HOVER_REQUEST: Request = serde_json::from_str(
         r#"{
             "jsonrpc":"2.0",
             "method":"textDocument/hover",
@@ -42,9 +41,8 @@ lazy_static! {
     )
     .unwrap();
 
-    static ref HOVER_RESPONSE: Outgoing = Outgoing::Response(
-        serde_json::from_str(
-            r#"
+    static ref HOVER_RESPONSE: Response = serde_json::from_str(
+        r#"
 {
    "jsonrpc":"2.0",
    "result":{
@@ -63,12 +61,11 @@ lazy_static! {
    "id":1
 }
 "#
-        )
-        .unwrap()
-    );
+    )
+    .unwrap();
 
 
-    static ref HOVER_ON_UNKNOWN_REQUEST: Incoming = serde_json::from_str(
+    static ref HOVER_ON_UNKNOWN_REQUEST: Request = serde_json::from_str(
         r#"{
             "jsonrpc":"2.0",
             "method":"textDocument/hover",
@@ -86,12 +83,12 @@ lazy_static! {
     )
     .unwrap();
 
-    static ref INITIALIZE_REQUEST: Incoming = serde_json::from_str(
+    static ref INITIALIZE_REQUEST: Request = serde_json::from_str(
         r#"{"jsonrpc":"2.0","method":"initialize","params":{"capabilities":{}},"id":1}"#
     )
     .unwrap();
 
-    static ref WORKSPACE_CONFIGURATION_REQUEST_WITHOUT_LANGUAGE : Incoming = serde_json::from_str(
+    static ref WORKSPACE_CONFIGURATION_REQUEST_WITHOUT_LANGUAGE : Response = serde_json::from_str(
         r#"{"jsonrpc":"2.0","result": [
     {
         "translationFiles": {
@@ -114,8 +111,7 @@ lazy_static! {
     )
     .unwrap();
 
-        static ref HOVER_RESPONSE_WITHOUT_LANGUAGE: Outgoing = Outgoing::Response(
-        serde_json::from_str(
+        static ref HOVER_RESPONSE_WITHOUT_LANGUAGE: Response = serde_json::from_str(
             r#"
 {
    "jsonrpc":"2.0",
@@ -136,10 +132,9 @@ lazy_static! {
 }
 "#
         )
-        .unwrap()
-    );
+        .unwrap();
 
-    static ref WORKSPACE_CONFIGURATION_REQUEST_WITH_CUSTOM_DATA : Incoming = serde_json::from_str(
+    static ref WORKSPACE_CONFIGURATION_REQUEST_WITH_CUSTOM_DATA : Response = serde_json::from_str(
         r#"{"jsonrpc":"2.0","result": [
     {
         "translationFiles": {
@@ -163,8 +158,7 @@ lazy_static! {
     .unwrap();
 
 
-        static ref HOVER_RESPONSE_WITH_CUSTOM_DATA: Outgoing = Outgoing::Response(
-        serde_json::from_str(
+        static ref HOVER_RESPONSE_WITH_CUSTOM_DATA: Response = serde_json::from_str(
             r#"
 {
    "jsonrpc":"2.0",
@@ -185,8 +179,7 @@ lazy_static! {
 }
 "#
         )
-        .unwrap()
-    );
+        .unwrap();
 
 
 
@@ -214,10 +207,10 @@ async fn hover_on_unknown_key_returns_nothing() {
 
     assert_eq!(
         service.call(HOVER_ON_UNKNOWN_REQUEST.clone()).await,
-        Ok(Some(Outgoing::Response(Response::ok(
+        Ok(Some(Response::from_ok(
             tower_lsp::jsonrpc::Id::Number(1),
             serde_json::Value::default()
-        ))))
+        )))
     );
 }
 
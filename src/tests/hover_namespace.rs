@@ -0,0 +1,150 @@
+use tower_lsp::jsonrpc::{Request, Response};
+
+mod helpers;
+use helpers::*;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+lazy_static! {
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/namespaces/*/*.json"
+            ]
+        },
+        "fileName": {
+            "details": ""
+        },
+        "namespace": {
+            "default": "common"
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+    static ref DID_OPEN_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/didOpen",
+            "params":{
+                "textDocument": {
+                    "uri": "file:///somefile.js",
+                    "languageId": "javascript",
+                    "version": 1,
+                    "text": "const { t } = useTranslation('checkout');\n{t('buttons.save')}\n{t('common:buttons.save')}"
+                }
+            }
+        }"#
+    )
+    .unwrap();
+    static ref HOVER_REQUEST_USE_TRANSLATION: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/hover",
+            "params": {
+                "textDocument": {
+                    "uri": "file:///somefile.js"
+                },
+                "position": {
+                    "line": 1,
+                    "character": 5
+                }
+            },
+            "id":1
+        }"#
+    )
+    .unwrap();
+    static ref HOVER_RESPONSE_USE_TRANSLATION: Response = serde_json::from_str(
+        r#"
+{
+   "jsonrpc":"2.0",
+   "result":{
+      "contents":"|translation|\n|-|\n|Place order|",
+      "range":{
+         "end":{
+            "character": 16,
+            "line": 1
+         },
+         "start":{
+            "character": 4,
+            "line": 1
+         }
+      }
+   },
+   "id":1
+}
+"#
+    )
+    .unwrap();
+    static ref HOVER_REQUEST_EXPLICIT_NAMESPACE: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"textDocument/hover",
+            "params": {
+                "textDocument": {
+                    "uri": "file:///somefile.js"
+                },
+                "position": {
+                    "line": 2,
+                    "character": 5
+                }
+            },
+            "id":1
+        }"#
+    )
+    .unwrap();
+    static ref HOVER_RESPONSE_EXPLICIT_NAMESPACE: Response = serde_json::from_str(
+        r#"
+{
+   "jsonrpc":"2.0",
+   "result":{
+      "contents":"|translation|\n|-|\n|Save|",
+      "range":{
+         "end":{
+            "character": 23,
+            "line": 2
+         },
+         "start":{
+            "character": 4,
+            "line": 2
+         }
+      }
+   },
+   "id":1
+}
+"#
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn hover_uses_namespace_of_use_translation() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    assert_eq!(service.call(DID_OPEN_REQUEST.clone()).await, Ok(None));
+
+    assert_eq!(
+        service.call(HOVER_REQUEST_USE_TRANSLATION.clone()).await,
+        Ok(Some(HOVER_RESPONSE_USE_TRANSLATION.clone()))
+    );
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn hover_uses_explicit_namespace() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    assert_eq!(service.call(DID_OPEN_REQUEST.clone()).await, Ok(None));
+
+    assert_eq!(
+        service.call(HOVER_REQUEST_EXPLICIT_NAMESPACE.clone()).await,
+        Ok(Some(HOVER_RESPONSE_EXPLICIT_NAMESPACE.clone()))
+    );
+}
@@ -1,4 +1,4 @@
-use tower_lsp::jsonrpc::{Incoming, Outgoing};
+use tower_lsp::jsonrpc::{Request, Response};
 
 mod helpers;
 use helpers::*;
@@ -7,7 +7,7 @@ use helpers::*;
 use pretty_assertions::assert_eq;
 
 lazy_static! {
-    static ref DID_OPEN_REQUEST: Incoming = serde_json::from_str(
+    static ref DID_OPEN_REQUEST: Request = serde_json::from_str(
         r#"{
             "jsonrpc":"2.0",
             "method":"textDocument/didOpen",
@@ -18,12 +18,11 @@ lazy_static! {
                     "version": 1,
                     "text": "translate('test');"
                 }
-            },
-            "id":1
+            }
         }"#
     )
     .unwrap();
-    static ref WORKSPACE_CONFIGURATION_REQUEST_WITH_LANGUAGE: Incoming = serde_json::from_str(
+    static ref WORKSPACE_CONFIGURATION_REQUEST_WITH_LANGUAGE: Response = serde_json::from_str(
         r#"{"jsonrpc":"2.0","result": [
     {
         "translationFiles": {
@@ -44,7 +43,7 @@ lazy_static! {
 ], "id": 1 }"#
     )
     .unwrap();
-    static ref HOVER_REQUEST: Incoming = serde_json::from_str(
+    static ref HOVER_REQUEST: Request = serde_json::from_str(
         r#"{
             "jsonrpc":"2.0",
             "method":"textDocument/hover",
@@ -61,9 +60,8 @@ lazy_static! {
         }"#
     )
     .unwrap();
-    static ref HOVER_RESPONSE_WITH_LANGUAGE: Outgoing = Outgoing::Response(
-        serde_json::from_str(
-            r#"
+    static ref HOVER_RESPONSE_WITH_LANGUAGE: Response = serde_json::from_str(
+        r#"
 {
    "jsonrpc":"2.0",
    "result":{
@@ -82,14 +80,13 @@ lazy_static! {
    "id":1
 }
 "#
-        )
-        .unwrap()
-    );
-    static ref INITIALIZE_REQUEST: Incoming = serde_json::from_str(
+    )
+    .unwrap();
+    static ref INITIALIZE_REQUEST: Request = serde_json::from_str(
         r#"{"jsonrpc":"2.0","method":"initialize","params":{"capabilities":{}},"id":1}"#
     )
     .unwrap();
-    static ref WORKSPACE_CONFIGURATION_REQUEST_WITHOUT_LANGUAGE: Incoming = serde_json::from_str(
+    static ref WORKSPACE_CONFIGURATION_REQUEST_WITHOUT_LANGUAGE: Response = serde_json::from_str(
         r#"{"jsonrpc":"2.0","result": [
     {
         "translationFiles": {
@@ -110,9 +107,8 @@ lazy_static! {
 ], "id": 1 }"#
     )
     .unwrap();
-    static ref HOVER_RESPONSE_WITHOUT_LANGUAGE: Outgoing = Outgoing::Response(
-        serde_json::from_str(
-            r#"
+    static ref HOVER_RESPONSE_WITHOUT_LANGUAGE: Response = serde_json::from_str(
+        r#"
 {
    "jsonrpc":"2.0",
    "result":{
@@ -131,9 +127,8 @@ lazy_static! {
    "id":1
 }
 "#
-        )
-        .unwrap()
-    );
+    )
+    .unwrap();
 }
 
 #[tokio::test]
@@ -0,0 +1,127 @@
+use super::*;
+
+#[test]
+fn parses_plural() {
+    assert_eq!(
+        parse_message("{count, plural, one {# item} other {# items}}"),
+        Ok(vec![IcuPart::Select {
+            name: "count".to_string(),
+            kind: SelectKind::Plural,
+            options: vec![
+                IcuOption {
+                    selector: "one".to_string(),
+                    message: vec![IcuPart::Pound, IcuPart::Text(" item".to_string())],
+                },
+                IcuOption {
+                    selector: "other".to_string(),
+                    message: vec![IcuPart::Pound, IcuPart::Text(" items".to_string())],
+                },
+            ],
+        }])
+    );
+}
+
+#[test]
+fn parses_arguments_and_quotes() {
+    assert_eq!(
+        parse_message("It''s '{'{name}'}' on {date, date, short}"),
+        Ok(vec![
+            IcuPart::Text("It's {".to_string()),
+            IcuPart::Argument {
+                name: "name".to_string(),
+                format: None
+            },
+            IcuPart::Text("} on ".to_string()),
+            IcuPart::Argument {
+                name: "date".to_string(),
+                format: Some("date".to_string())
+            },
+        ])
+    );
+}
+
+#[test]
+fn skips_plural_offset() {
+    let expected = parse_message("{count, plural, =0 {nobody} other {# others}}");
+
+    assert!(expected.is_ok());
+    assert_eq!(
+        parse_message("{count, plural, offset:1 =0 {nobody} other {# others}}"),
+        expected
+    );
+    assert_eq!(
+        parse_message("{count, plural, offset: 1 =0 {nobody} other {# others}}"),
+        expected
+    );
+}
+
+#[test]
+fn reports_plural_offset_without_number() {
+    assert_eq!(
+        parse_message("{count, plural, offset: other {# others}}"),
+        Err(IcuError {
+            message: "Expected a number after `offset:`".to_string(),
+            offset: 16
+        })
+    );
+}
+
+#[test]
+fn reports_unclosed_brace() {
+    assert_eq!(
+        parse_message("Hi {name"),
+        Err(IcuError {
+            message: "Unclosed `{`".to_string(),
+            offset: 3
+        })
+    );
+}
+
+#[test]
+fn reports_unexpected_brace() {
+    assert_eq!(
+        parse_message("Hi name}"),
+        Err(IcuError {
+            message: "Unexpected `}`".to_string(),
+            offset: 7
+        })
+    );
+}
+
+#[test]
+fn reports_missing_other() {
+    assert_eq!(
+        parse_message("{gender, select, male {He}}")
+            .unwrap_err()
+            .message,
+        "`gender` is missing the required `other` option"
+    );
+}
+
+#[test]
+fn reports_plural_categories_of_language() {
+    let parts =
+        parse_message("{count, plural, =0 {none} one {#} few {#} lots {#} other {#}}").unwrap();
+
+    assert_eq!(
+        get_plural_category_errors(&parts, Some("en")),
+        vec![
+            "`few` is not a plural category of `en`",
+            "Unknown plural category `lots`"
+        ]
+    );
+    assert_eq!(
+        get_plural_category_errors(&parts, Some("ru")),
+        vec!["Unknown plural category `lots`"]
+    );
+}
+
+#[test]
+fn renders_options() {
+    let parts = parse_message("You have {count, plural, one {# item} other {# items}}.").unwrap();
+
+    assert_eq!(
+        render_message(&parts),
+        "You have [count: **one** # item · **other** # items]."
+    );
+}
@@ -0,0 +1,143 @@
+use serde_json::json;
+use tower_lsp::jsonrpc::{Request, Response};
+
+mod helpers;
+use helpers::*;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+lazy_static! {
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/placeholders/en.json"
+            ]
+        },
+        "fileName": {
+            "details": "(?P<language>.+?)\\."
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+}
+
+fn did_open_request(text: &str) -> Request {
+    serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didOpen",
+        "params": {
+            "textDocument": {
+                "uri": "file:///somefile.js",
+                "languageId": "javascript",
+                "version": 1,
+                "text": text
+            }
+        }
+    }))
+    .unwrap()
+}
+
+fn completion_request(line: u32, character: u32) -> Request {
+    serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/completion",
+        "params": {
+            "textDocument": {
+                "uri": "file:///somefile.js"
+            },
+            "position": {
+                "line": line,
+                "character": character
+            }
+        },
+        "id": 2
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn completes_placeholder_names() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    assert_eq!(
+        service
+            .call(did_open_request("translate('greeting', {  })"))
+            .await,
+        Ok(None)
+    );
+
+    let result = service
+        .call(completion_request(0, 24))
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(result).unwrap()["result"],
+        json!([{
+            "label": "name",
+            "kind": 6,
+            "detail": "Placeholder of greeting"
+        }])
+    );
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn does_not_complete_passed_placeholders() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    assert_eq!(
+        service
+            .call(did_open_request("translate('greeting', { name: 'x', })"))
+            .await,
+        Ok(None)
+    );
+
+    let result = service
+        .call(completion_request(0, 35))
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(serde_json::to_value(result).unwrap()["result"], json!([]));
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn reports_missing_placeholders() {
+    let (mut service, mut messages) =
+        prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let diagnostics = call_and_wait_for_diagnostics(
+        &mut service,
+        &mut messages,
+        did_open_request(
+            "translate('greeting')\ntranslate('greeting', { name })\ntranslate('greeting', { ...props })",
+        ),
+        "file:///somefile.js",
+    )
+    .await;
+
+    assert_eq!(
+        diagnostics,
+        json!([{
+            "range": {
+                "start": { "line": 0, "character": 11 },
+                "end": { "line": 0, "character": 19 }
+            },
+            "severity": 2,
+            "code": "missing-placeholder",
+            "source": "lsp-translations",
+            "message": "`greeting` expects the placeholder `name`"
+        }])
+    );
+}
@@ -0,0 +1,65 @@
+use super::*;
+
+use regex::Regex;
+use std::path::PathBuf;
+
+use crate::RequiredPrefixConfig;
+
+fn create_config() -> KeyNamingConfig {
+    KeyNamingConfig {
+        segment: Some(Regex::new("^[a-z0-9]+(_[a-z0-9]+)*$").unwrap()),
+        max_depth: Some(3),
+        forbidden_characters: " ".to_string(),
+        required_prefixes: vec![RequiredPrefixConfig {
+            file: Regex::new("admin\\.json$").unwrap(),
+            prefix: "admin.".to_string(),
+        }],
+    }
+}
+
+#[test]
+fn accepts_valid_keys() {
+    assert!(get_key_naming_violations("buttons.save_button", None, &create_config()).is_empty());
+}
+
+#[test]
+fn reports_violations() {
+    assert_eq!(
+        get_key_naming_violations("a.b.Save Button.d", None, &create_config()),
+        vec![
+            "segment `Save Button` does not match `^[a-z0-9]+(_[a-z0-9]+)*$`",
+            "has 4 segments where at most 3 are allowed",
+            "contains the forbidden character ` `"
+        ]
+    );
+}
+
+#[test]
+fn reports_missing_prefix_per_file() {
+    let config = create_config();
+
+    assert_eq!(
+        get_key_naming_violations("title", Some(&PathBuf::from("/en/admin.json")), &config),
+        vec!["does not start with `admin.`"]
+    );
+    assert!(
+        get_key_naming_violations("title", Some(&PathBuf::from("/en/common.json")), &config)
+            .is_empty()
+    );
+}
+
+#[test]
+fn renames_keys_to_snake_case() {
+    let config = create_config();
+
+    assert_eq!(
+        get_renamed_key("\"saveButton\"", None, &config),
+        Some("\"save_button\"".to_string())
+    );
+    assert_eq!(
+        get_renamed_key("common:Buttons.Save now", Some(":"), &config),
+        Some("common:buttons.save_now".to_string())
+    );
+    assert_eq!(get_renamed_key("save_button", None, &config), None);
+    assert_eq!(get_renamed_key("ÿ€", None, &config), None);
+}
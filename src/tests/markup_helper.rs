@@ -0,0 +1,69 @@
+use super::*;
+
+use std::path::PathBuf;
+
+use crate::DefinitionSource;
+
+fn create_definition(value: &str, language: &str) -> Definition {
+    Definition {
+        key: "link".to_string(),
+        value: value.to_string(),
+        file: Some(DefinitionSource {
+            path: PathBuf::from(format!("/{}.json", language)),
+            extra_data: vec![("language".to_string(), language.to_string())]
+                .into_iter()
+                .collect(),
+        }),
+        ..Default::default()
+    }
+}
+
+fn tag(name: &str, kind: TagKind) -> Tag {
+    Tag {
+        name: name.to_string(),
+        kind,
+    }
+}
+
+#[test]
+fn finds_tags() {
+    assert_eq!(
+        get_tags(r#"Click <a href="/next">here</a><br> or <0>continue</0><1/> if 2 < 3"#),
+        vec![
+            tag("a", TagKind::Open),
+            tag("a", TagKind::Close),
+            tag("br", TagKind::SelfClosing),
+            tag("0", TagKind::Open),
+            tag("0", TagKind::Close),
+            tag("1", TagKind::SelfClosing),
+        ]
+    );
+}
+
+#[test]
+fn finds_unbalanced_tags() {
+    assert!(get_unbalanced_tags(&get_tags("<b>bold <i>both</i></b>")).is_empty());
+    assert_eq!(
+        get_unbalanced_tags(&get_tags("<b>bold <i>both</b></u>")),
+        vec!["`<i>` is not closed", "`</u>` has no opening tag"]
+    );
+    assert_eq!(
+        get_unbalanced_tags(&get_tags("<b>bold")),
+        vec!["`<b>` is not closed"]
+    );
+}
+
+#[test]
+fn compares_tags_with_reference_language() {
+    let reference = create_definition("Click <b>here</b> to <a>continue</a>", "en");
+    let translation = create_definition("Klik <i>hier</i> om <a>verder</a> te gaan", "nl");
+    let definitions = [&reference, &translation];
+
+    assert_eq!(
+        get_tag_mismatch(&translation, &definitions, "en")
+            .unwrap()
+            .to_string(),
+        "missing `<b>`, `</b>`, unexpected `<i>`, `</i>`"
+    );
+    assert_eq!(get_tag_mismatch(&reference, &definitions, "en"), None);
+}
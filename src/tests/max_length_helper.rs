@@ -0,0 +1,68 @@
+use super::*;
+
+use regex::Regex;
+use std::path::PathBuf;
+
+use crate::DefinitionSource;
+
+fn create_definition(key: &str, value: &str, language: &str) -> Definition {
+    Definition {
+        key: key.to_string(),
+        value: value.to_string(),
+        file: Some(DefinitionSource {
+            path: PathBuf::from(format!("/{}.arb", language)),
+            extra_data: vec![("language".to_string(), language.to_string())]
+                .into_iter()
+                .collect(),
+        }),
+        ..Default::default()
+    }
+}
+
+fn create_rule(key: &str, language: Option<&str>, max: usize) -> MaxLengthRule {
+    MaxLengthRule {
+        key: Regex::new(key).unwrap(),
+        language: language.map(String::from),
+        max,
+    }
+}
+
+#[test]
+fn counts_characters() {
+    assert_eq!(get_length("Geschäft"), 8);
+}
+
+#[test]
+fn uses_strictest_rule_of_language() {
+    let definition = create_definition("title", "Titel", "de");
+    let rules = [
+        create_rule("^title$", None, 24),
+        create_rule("^title$", Some("de"), 20),
+        create_rule("^title$", Some("en"), 10),
+        create_rule("^other$", None, 5),
+    ];
+
+    assert_eq!(
+        get_max_length(&definition, &MetadataMaxLengths::new(), &rules),
+        Some(20)
+    );
+}
+
+#[test]
+fn uses_arb_metadata_for_every_language() {
+    let definitions = [
+        create_definition("title", "Title", "en"),
+        create_definition("@title.maxLength", "12", "en"),
+        create_definition("title", "Titel", "de"),
+    ];
+    let metadata_max_lengths = get_metadata_max_lengths(&definitions);
+
+    assert_eq!(
+        get_max_length(&definitions[2], &metadata_max_lengths, &[]),
+        Some(12)
+    );
+    assert_eq!(
+        get_max_length(&definitions[1], &metadata_max_lengths, &[]),
+        None
+    );
+}
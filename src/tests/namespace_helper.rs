@@ -0,0 +1,146 @@
+use crate::{NamespaceConfig, NamespaceSource};
+
+use super::*;
+
+fn namespace_config() -> NamespaceConfig {
+    NamespaceConfig {
+        separator: ":".to_string(),
+        default: Some("common".to_string()),
+        from: NamespaceSource::FileName,
+    }
+}
+
+#[test]
+fn resolves_explicit_namespace() {
+    assert_eq!(
+        resolve_key("checkout:buttons.save", "", 0, &namespace_config()),
+        NamespacedKey {
+            namespace: Some("checkout".to_string()),
+            key: "buttons.save".to_string()
+        }
+    );
+}
+
+#[test]
+fn resolves_default_namespace() {
+    assert_eq!(
+        resolve_key("buttons.save", "t('buttons.save')", 3, &namespace_config()),
+        NamespacedKey {
+            namespace: Some("common".to_string()),
+            key: "buttons.save".to_string()
+        }
+    );
+}
+
+#[test]
+fn resolves_namespace_of_use_translation() {
+    let text = r#"
+        const { t } = useTranslation('checkout');
+        t('buttons.save');
+        "#;
+
+    assert_eq!(
+        resolve_key("buttons.save", text, 62, &namespace_config()),
+        NamespacedKey {
+            namespace: Some("checkout".to_string()),
+            key: "buttons.save".to_string()
+        }
+    );
+}
+
+#[test]
+fn resolves_key_prefix_of_use_translation() {
+    let text = r#"
+        const { t } = useTranslation('checkout', { keyPrefix: 'buttons' });
+        t('save');
+        "#;
+
+    assert_eq!(
+        resolve_key("save", text, 88, &namespace_config()),
+        NamespacedKey {
+            namespace: Some("checkout".to_string()),
+            key: "buttons.save".to_string()
+        }
+    );
+}
+
+#[test]
+fn uses_closest_use_translation_before_key() {
+    let text = r#"
+        function First() {
+            const { t } = useTranslation('first');
+        }
+        function Second() {
+            const { t } = useTranslation(['second', 'common']);
+            t('title');
+        }
+        "#;
+
+    assert_eq!(
+        find_translation_scope(text, text.find("t('title')").unwrap()),
+        TranslationScope {
+            namespace: Some("second".to_string()),
+            key_prefix: None
+        }
+    );
+}
+
+#[test]
+fn uses_default_scope_before_first_use_translation() {
+    let text = r#"
+        const title = t('title');
+        const { t } = useTranslation('checkout', { keyPrefix: 'buttons' });
+        "#;
+
+    assert_eq!(
+        find_translation_scope(text, text.find("t('title')").unwrap()),
+        TranslationScope::default()
+    );
+    assert_eq!(
+        resolve_key(
+            "title",
+            text,
+            text.find("t('title')").unwrap(),
+            &namespace_config()
+        ),
+        NamespacedKey {
+            namespace: Some("common".to_string()),
+            key: "title".to_string()
+        }
+    );
+}
+
+#[test]
+fn shortens_key_in_same_namespace() {
+    let scope = TranslationScope {
+        namespace: Some("checkout".to_string()),
+        key_prefix: Some("buttons".to_string()),
+    };
+
+    assert_eq!(
+        get_key_for_scope(
+            &NamespacedKey {
+                namespace: Some("checkout".to_string()),
+                key: "buttons.save".to_string()
+            },
+            &scope,
+            &namespace_config()
+        ),
+        "save"
+    );
+}
+
+#[test]
+fn prefixes_key_in_other_namespace() {
+    assert_eq!(
+        get_key_for_scope(
+            &NamespacedKey {
+                namespace: Some("checkout".to_string()),
+                key: "buttons.save".to_string()
+            },
+            &TranslationScope::default(),
+            &namespace_config()
+        ),
+        "checkout:buttons.save"
+    );
+}
@@ -0,0 +1,110 @@
+use super::*;
+
+use crate::DefinitionSource;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+static ALL_SYNTAXES: &[PlaceholderSyntax] = &[
+    PlaceholderSyntax::I18next,
+    PlaceholderSyntax::Rails,
+    PlaceholderSyntax::Printf,
+    PlaceholderSyntax::Icu,
+];
+
+fn get_placeholder_texts(value: &str) -> Vec<String> {
+    get_placeholders(value, ALL_SYNTAXES)
+        .iter()
+        .map(|placeholder| placeholder.to_string())
+        .collect()
+}
+
+fn create_definition(language: &str, value: &str) -> Definition {
+    let mut extra_data = HashMap::<String, String>::new();
+    extra_data.insert("language".to_string(), language.to_string());
+
+    Definition {
+        key: "greeting".to_string(),
+        value: value.to_string(),
+        file: Some(DefinitionSource {
+            path: PathBuf::from(format!("/{}.json", language)),
+            extra_data,
+        }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn parses_i18next_placeholders() {
+    assert_eq!(
+        get_placeholder_texts("Hello {{name}}, {{- html}} {{ date, datetime }}"),
+        vec!["{{name}}", "{{html}}", "{{date}}"]
+    );
+}
+
+#[test]
+fn parses_icu_placeholders() {
+    assert_eq!(
+        get_placeholder_texts("{name} has {count, plural, one {# item} other {# items}}"),
+        vec!["{name}", "{count}"]
+    );
+}
+
+#[test]
+fn parses_printf_placeholders() {
+    assert_eq!(
+        get_placeholder_texts("%s of %2$d, 100%%"),
+        vec!["%1$s", "%2$d"]
+    );
+}
+
+#[test]
+fn parses_rails_placeholders() {
+    assert_eq!(get_placeholder_texts("Hi %{name}"), vec!["%{name}"]);
+}
+
+#[test]
+fn printf_is_opt_in() {
+    assert_eq!(
+        get_placeholders("50% discount", &crate::default_placeholder_syntax()),
+        vec![]
+    );
+}
+
+#[test]
+fn reports_placeholder_mismatch() {
+    let en = create_definition("en", "Hello {{name}}");
+    let nl = create_definition("nl", "Hallo {{naam}}");
+
+    let mismatch = get_placeholder_mismatch(&nl, &[&en, &nl], "en", ALL_SYNTAXES).unwrap();
+
+    assert_eq!(
+        mismatch.to_string(),
+        "missing `{{name}}`, unexpected `{{naam}}`"
+    );
+    assert_eq!(
+        get_placeholder_mismatch(&en, &[&en, &nl], "en", ALL_SYNTAXES),
+        None
+    );
+}
+
+#[test]
+fn accepts_reordered_placeholders() {
+    let en = create_definition("en", "{{count}} of {{total}}");
+    let nl = create_definition("nl", "{{total}} waarvan {{count}}");
+
+    assert_eq!(
+        get_placeholder_mismatch(&nl, &[&en, &nl], "en", ALL_SYNTAXES),
+        None
+    );
+}
+
+#[test]
+fn removes_placeholders() {
+    assert_eq!(
+        remove_placeholders(
+            "{{count}} items for %{name}",
+            &[PlaceholderSyntax::I18next, PlaceholderSyntax::Rails]
+        ),
+        " items for "
+    );
+}
@@ -0,0 +1,55 @@
+use crate::PluralConfig;
+
+use super::*;
+
+fn plural_config(separator: &str) -> PluralConfig {
+    PluralConfig {
+        separator: separator.to_string(),
+        categories: PLURAL_CATEGORIES
+            .iter()
+            .map(|category| category.to_string())
+            .collect(),
+    }
+}
+
+#[test]
+fn splits_suffix_plural_category() {
+    assert_eq!(
+        split_plural_category("cart.item_one", &plural_config("_")),
+        Some(("cart.item".to_string(), "one".to_string()))
+    );
+}
+
+#[test]
+fn splits_segment_plural_category() {
+    assert_eq!(
+        split_plural_category("cart.item.other", &plural_config(".")),
+        Some(("cart.item".to_string(), "other".to_string()))
+    );
+}
+
+#[test]
+fn does_not_split_unknown_category() {
+    assert_eq!(
+        split_plural_category("cart.item_count", &plural_config("_")),
+        None
+    );
+}
+
+#[test]
+fn does_not_split_category_without_key() {
+    assert_eq!(split_plural_category("_one", &plural_config("_")), None);
+}
+
+#[test]
+fn gets_plural_categories_for_region() {
+    assert_eq!(
+        get_plural_categories("ru-RU"),
+        Some(&["one", "few", "many", "other"][..])
+    );
+}
+
+#[test]
+fn gets_no_plural_categories_for_unknown_language() {
+    assert_eq!(get_plural_categories("tlh"), None);
+}
@@ -0,0 +1,238 @@
+use super::*;
+
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use tower_lsp::jsonrpc::Request;
+use tower_lsp::lsp_types::{DiagnosticSeverity, Position, Range};
+use tower_lsp::LspService;
+use tower_test::mock::Spawn;
+
+fn create_diagnostic(message: &str) -> Diagnostic {
+    Diagnostic {
+        range: Range::new(Position::new(1, 2), Position::new(1, 8)),
+        severity: Some(DiagnosticSeverity::WARNING),
+        message: message.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn reports_full_diagnostics_without_previous_result() {
+    let diagnostics = vec![create_diagnostic("Unused key")];
+
+    assert_eq!(
+        get_report(diagnostics.clone(), None),
+        DocumentDiagnosticReportKind::Full(FullDocumentDiagnosticReport {
+            result_id: Some(get_result_id(&diagnostics)),
+            items: diagnostics,
+        })
+    );
+}
+
+#[test]
+fn reports_unchanged_diagnostics() {
+    let diagnostics = vec![create_diagnostic("Unused key")];
+    let result_id = get_result_id(&diagnostics);
+
+    assert_eq!(
+        get_report(diagnostics, Some(&result_id)),
+        DocumentDiagnosticReportKind::Unchanged(UnchangedDocumentDiagnosticReport { result_id })
+    );
+}
+
+#[test]
+fn changes_result_id_with_diagnostics() {
+    assert_ne!(
+        get_result_id(&[create_diagnostic("Unused key")]),
+        get_result_id(&[create_diagnostic("Duplicate key")])
+    );
+    assert_eq!(get_result_id(&[]), get_result_id(&[]));
+}
+
+#[test]
+fn reads_support_from_capabilities() {
+    assert_eq!(
+        PullDiagnosticsSupport::from_capabilities(&ClientCapabilities::default()),
+        PullDiagnosticsSupport::default()
+    );
+    assert_eq!(
+        PullDiagnosticsSupport::from_capabilities(
+            &serde_json::from_value(json!({
+                "textDocument": { "diagnostic": { "dynamicRegistration": false } },
+                "workspace": { "diagnostics": { "refreshSupport": true } },
+            }))
+            .unwrap()
+        ),
+        PullDiagnosticsSupport {
+            enabled: true,
+            refresh: true,
+        }
+    );
+}
+
+#[test]
+fn reads_disabled_refresh_support() {
+    assert_eq!(
+        PullDiagnosticsSupport::from_capabilities(
+            &serde_json::from_value(json!({
+                "textDocument": { "diagnostic": {} },
+                "workspace": { "diagnostic": { "refreshSupport": false } },
+            }))
+            .unwrap()
+        ),
+        PullDiagnosticsSupport {
+            enabled: true,
+            refresh: false,
+        }
+    );
+}
+
+/// Sends `message` to the server and returns the result of its response
+async fn call(service: &mut Spawn<LspService<Backend>>, message: Value) -> Option<Value> {
+    let request: Request = serde_json::from_value(message).unwrap();
+    let response = service.call(request).await.unwrap()?;
+
+    Some(response.result().cloned().unwrap_or_default())
+}
+
+/// Initializes a server for a client with `capabilities`
+async fn initialize(capabilities: Value) -> (Spawn<LspService<Backend>>, Value) {
+    let (service, _) = LspService::new(Backend::new);
+    let mut service = Spawn::new(service);
+
+    let result = call(
+        &mut service,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "method": "initialize",
+            "params": { "capabilities": capabilities },
+        }),
+    )
+    .await
+    .unwrap();
+
+    (service, result)
+}
+
+#[tokio::test]
+#[timeout(2000)]
+async fn answers_pull_diagnostics_requests() {
+    let (mut service, result) = initialize(json!({ "textDocument": { "diagnostic": {} } })).await;
+
+    assert_eq!(
+        result["capabilities"]["diagnosticProvider"],
+        json!({
+            "identifier": "lsp-translations",
+            "interFileDependencies": true,
+            "workspaceDiagnostics": true,
+        })
+    );
+
+    let result = call(
+        &mut service,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "textDocument/diagnostic",
+            "params": { "textDocument": { "uri": "file:///missing.js" } },
+        }),
+    )
+    .await;
+
+    let result_id = get_result_id(&[]);
+    assert_eq!(
+        result,
+        Some(json!({ "kind": "full", "resultId": result_id, "items": [] }))
+    );
+
+    let result = call(
+        &mut service,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "textDocument/diagnostic",
+            "params": {
+                "textDocument": { "uri": "file:///missing.js" },
+                "previousResultId": result_id,
+            },
+        }),
+    )
+    .await;
+
+    assert_eq!(
+        result,
+        Some(json!({ "kind": "unchanged", "resultId": result_id }))
+    );
+
+    let result = call(
+        &mut service,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "workspace/diagnostic",
+            "params": { "previousResultIds": [] },
+        }),
+    )
+    .await;
+
+    assert_eq!(result, Some(json!({ "items": [] })));
+}
+
+#[tokio::test]
+#[timeout(2000)]
+async fn reports_versions_of_changed_documents() {
+    let (mut service, _) = initialize(json!({ "textDocument": { "diagnostic": {} } })).await;
+
+    call(
+        &mut service,
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": {
+                "textDocument": {
+                    "uri": "file:///index.js",
+                    "languageId": "javascript",
+                    "version": 1,
+                    "text": "translate('first')",
+                },
+            },
+        }),
+    )
+    .await;
+    call(
+        &mut service,
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didChange",
+            "params": {
+                "textDocument": { "uri": "file:///index.js", "version": 2 },
+                "contentChanges": [{ "text": "translate('second')" }],
+            },
+        }),
+    )
+    .await;
+
+    let result = call(
+        &mut service,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "workspace/diagnostic",
+            "params": { "previousResultIds": [] },
+        }),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result["items"][0]["uri"], json!("file:///index.js"));
+    assert_eq!(result["items"][0]["version"], json!(2));
+}
+
+#[tokio::test]
+#[timeout(2000)]
+async fn does_not_advertise_pull_diagnostics_without_support() {
+    let (_, result) = initialize(json!({})).await;
+
+    assert_eq!(result["capabilities"].get("diagnosticProvider"), None);
+}
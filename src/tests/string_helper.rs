@@ -1,4 +1,7 @@
-use crate::string_helper::is_editing_position;
+use crate::string_helper::{
+    find_interpolation_object, find_translation_keys, get_next_key_segment, get_object_keys,
+    is_editing_position,
+};
 use lsp_document::IndexedText;
 
 use super::*;
@@ -168,3 +171,106 @@ fn is_editing_position_returns_false_for_other_functions() {
         false
     );
 }
+
+#[test]
+fn next_key_segment_is_group() {
+    assert_eq!(
+        get_next_key_segment("checkout.cart.title", "checkout.ca"),
+        Some(("cart".to_string(), true))
+    );
+}
+
+#[test]
+fn next_key_segment_is_leaf() {
+    assert_eq!(
+        get_next_key_segment("checkout.title", "checkout."),
+        Some(("title".to_string(), false))
+    );
+}
+
+#[test]
+fn next_key_segment_of_empty_key() {
+    assert_eq!(
+        get_next_key_segment("checkout.title", ""),
+        Some(("checkout".to_string(), true))
+    );
+}
+
+#[test]
+fn next_key_segment_of_other_parent() {
+    assert_eq!(get_next_key_segment("account.title", "checkout."), None);
+}
+
+#[test]
+fn match_score_prefers_prefix() {
+    assert_eq!(get_match_score("checkout.title", "check"), Some(0));
+}
+
+#[test]
+fn match_score_of_substring() {
+    assert_eq!(get_match_score("checkout.title", "TITLE"), Some(1009));
+}
+
+#[test]
+fn match_score_of_fuzzy_match() {
+    assert_eq!(get_match_score("checkout.title", "cotitle"), Some(2007));
+}
+
+#[test]
+fn match_score_without_match() {
+    assert_eq!(get_match_score("checkout.title", "cart"), None);
+}
+
+#[test]
+fn value_match_score_ranks_below_keys() {
+    assert_eq!(get_value_match_score("Your Cart", "cart"), Some(3005));
+}
+
+#[test]
+fn finds_all_translation_keys() {
+    assert_eq!(
+        find_translation_keys("translate('a.b') + I18n.t(\"c\")")
+            .iter()
+            .map(|key| key.as_str())
+            .collect::<Vec<&str>>(),
+        vec!["a.b", "c"]
+    );
+}
+
+#[test]
+fn finds_interpolation_object() {
+    let text = "t('greeting', { name: user.name }) + t('other')";
+
+    assert_eq!(
+        find_interpolation_object(text, 11).map(|body| &text[body]),
+        Some(" name: user.name ")
+    );
+    assert_eq!(find_interpolation_object(text, 45), None);
+}
+
+#[test]
+fn finds_unterminated_interpolation_object() {
+    let text = "t('greeting', { na";
+
+    assert_eq!(
+        find_interpolation_object(text, 11).map(|body| &text[body]),
+        Some(" na")
+    );
+}
+
+#[test]
+fn gets_object_keys() {
+    assert_eq!(
+        get_object_keys(" name, 'count': 1, nested: { a: 1, b: 2 } "),
+        Some(vec![
+            "name".to_string(),
+            "count".to_string(),
+            "nested".to_string()
+        ])
+    );
+}
+
+#[test]
+fn object_keys_of_spread_are_unknown() {
+    assert_eq!(get_object_keys(" ...props, name "), None);
+}
@@ -0,0 +1,355 @@
+use serde_json::{json, Value};
+use std::env;
+use tower_lsp::{
+    jsonrpc::{Request, Response},
+    lsp_types::Url,
+};
+
+mod helpers;
+use helpers::*;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+lazy_static! {
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/translation_file_features/*.json"
+            ]
+        },
+        "sourceFiles": {
+            "include": [
+                "./fixtures/translation_file_features/*.js"
+            ]
+        },
+        "fileName": {
+            "details": "(?P<language>.+?)\\."
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+    static ref KEY_FILTER_WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/translation_file_key_filter/*.json"
+            ]
+        },
+        "fileName": {
+            "details": "(?P<language>.+?)\\."
+        },
+        "key": {
+            "filter": "^[^.]+\\.(.*)$"
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+    static ref EN_URI: Url = Url::from_file_path(
+        env::current_dir()
+            .unwrap()
+            .join("src/tests/fixtures/translation_file_features/en.json")
+    )
+    .unwrap();
+    static ref NL_URI: Url = Url::from_file_path(
+        env::current_dir()
+            .unwrap()
+            .join("src/tests/fixtures/translation_file_features/nl.json")
+    )
+    .unwrap();
+}
+
+fn did_open_request(text: &str) -> Request {
+    serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didOpen",
+        "params": {
+            "textDocument": {
+                "uri": *NL_URI,
+                "languageId": "json",
+                "version": 1,
+                "text": text
+            }
+        }
+    }))
+    .unwrap()
+}
+
+fn position_request(method: &str, line: u32, character: u32) -> Request {
+    serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": method,
+        "params": {
+            "textDocument": {
+                "uri": *NL_URI
+            },
+            "position": {
+                "line": line,
+                "character": character
+            }
+        },
+        "id": 2
+    }))
+    .unwrap()
+}
+
+fn get_labels(result: Value) -> Vec<String> {
+    let mut labels = result
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["label"].as_str().unwrap().to_string())
+        .collect::<Vec<String>>();
+
+    labels.sort();
+    labels
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn hover_on_key_in_translation_file() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    assert_eq!(
+        service
+            .call(did_open_request(
+                "{\n  \"checkout\": {\n    \"title\": \"Afrekenen\"\n  }\n}\n"
+            ))
+            .await,
+        Ok(None)
+    );
+
+    let result = service
+        .call(position_request("textDocument/hover", 2, 7))
+        .await
+        .unwrap()
+        .unwrap();
+
+    let contents = serde_json::to_value(result).unwrap()["result"]["contents"].clone();
+    let contents = contents.as_str().unwrap();
+
+    assert!(contents.contains("Checkout"));
+    assert!(contents.contains("Afrekenen"));
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn hover_on_changed_translation_file() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    assert_eq!(
+        service
+            .call(did_open_request(
+                "{\n  \"checkout\": {\n    \"title\": \"Afrekenen\"\n  }\n}\n"
+            ))
+            .await,
+        Ok(None)
+    );
+
+    // Both keys are written on the first line of the unsaved document
+    let did_change: Request = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didChange",
+        "params": {
+            "textDocument": {
+                "uri": *NL_URI,
+                "version": 2
+            },
+            "contentChanges": [{
+                "text": "{ \"greeting\": \"Hallo\", \"checkout\": { \"title\": \"Afrekenen\" } }\n"
+            }]
+        }
+    }))
+    .unwrap();
+    assert_eq!(service.call(did_change).await, Ok(None));
+
+    let get_contents = |result: Response| {
+        serde_json::to_value(result).unwrap()["result"]["contents"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    };
+
+    let result = service
+        .call(position_request("textDocument/hover", 0, 4))
+        .await
+        .unwrap()
+        .unwrap();
+    let contents = get_contents(result);
+
+    assert!(contents.contains("Hello"));
+    assert!(contents.contains("Hallo"));
+
+    let result = service
+        .call(position_request("textDocument/hover", 0, 40))
+        .await
+        .unwrap()
+        .unwrap();
+    let contents = get_contents(result);
+
+    assert!(contents.contains("Checkout"));
+    assert!(contents.contains("Afrekenen"));
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn completion_of_missing_keys_in_group() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    assert_eq!(
+        service
+            .call(did_open_request(
+                "{\n  \"checkout\": {\n    \"title\": \"Afrekenen\",\n    \"\"\n  }\n}\n"
+            ))
+            .await,
+        Ok(None)
+    );
+
+    let result = service
+        .call(position_request("textDocument/completion", 3, 5))
+        .await
+        .unwrap()
+        .unwrap();
+
+    let items = serde_json::to_value(result).unwrap()["result"].clone();
+
+    assert_eq!(get_labels(items.clone()), vec!["pay"]);
+    assert_eq!(items[0]["detail"], "Pay now");
+    assert_eq!(
+        items[0]["textEdit"]["range"],
+        json!({
+            "start": { "line": 3, "character": 5 },
+            "end": { "line": 3, "character": 5 }
+        })
+    );
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn completion_of_missing_keys_in_root() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    assert_eq!(
+        service
+            .call(did_open_request(
+                "{\n  \"gr\"\n  \"checkout\": {\n    \"title\": \"Afrekenen\"\n  }\n}\n"
+            ))
+            .await,
+        Ok(None)
+    );
+
+    let result = service
+        .call(position_request("textDocument/completion", 1, 5))
+        .await
+        .unwrap()
+        .unwrap();
+
+    let items = serde_json::to_value(result).unwrap()["result"].clone();
+
+    assert_eq!(get_labels(items), vec!["checkout.pay", "greeting"]);
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn code_lens_shows_usages_and_missing_languages() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let code_lens_request: Request = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/codeLens",
+        "params": {
+            "textDocument": {
+                "uri": *EN_URI
+            }
+        },
+        "id": 2
+    }))
+    .unwrap();
+
+    let result = service.call(code_lens_request).await.unwrap().unwrap();
+    let code_lenses = serde_json::to_value(result).unwrap()["result"].clone();
+
+    let mut titles = vec![];
+    for code_lens in code_lenses.as_array().unwrap() {
+        let resolve_request: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "method": "codeLens/resolve",
+            "params": code_lens,
+            "id": 3
+        }))
+        .unwrap();
+
+        let result = service.call(resolve_request).await.unwrap().unwrap();
+        let command = serde_json::to_value(result).unwrap()["result"]["command"].clone();
+
+        assert_eq!(command["command"], "editor.action.showReferences");
+        titles.push(command["title"].as_str().unwrap().to_string());
+    }
+
+    titles.sort();
+    assert_eq!(
+        titles,
+        vec![
+            "used 0 times · missing in nl",
+            "used 1 time · missing in nl",
+            "used 2 times",
+        ]
+    );
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn completion_of_missing_keys_with_key_filter() {
+    let (mut service, _) =
+        prepare_with_workspace_config(&KEY_FILTER_WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let nl_uri = Url::from_file_path(
+        env::current_dir()
+            .unwrap()
+            .join("src/tests/fixtures/translation_file_key_filter/nl.json"),
+    )
+    .unwrap();
+
+    let did_open: Request = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didOpen",
+        "params": {
+            "textDocument": {
+                "uri": nl_uri,
+                "languageId": "json",
+                "version": 1,
+                "text": "{\n  \"nl\": {\n    \"checkout\": {\n      \"title\": \"Afrekenen\",\n      \"\"\n    }\n  }\n}\n"
+            }
+        }
+    }))
+    .unwrap();
+    assert_eq!(service.call(did_open).await, Ok(None));
+
+    let completion: Request = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/completion",
+        "params": {
+            "textDocument": { "uri": nl_uri },
+            "position": { "line": 4, "character": 7 }
+        },
+        "id": 2
+    }))
+    .unwrap();
+
+    let result = service.call(completion).await.unwrap().unwrap();
+    let items = serde_json::to_value(result).unwrap()["result"].clone();
+
+    // The locale roots `en` and `nl` are removed by the key filter
+    assert_eq!(get_labels(items), vec!["pay"]);
+}
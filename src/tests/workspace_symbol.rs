@@ -0,0 +1,70 @@
+use tower_lsp::jsonrpc::{Request, Response};
+
+mod helpers;
+use helpers::*;
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+lazy_static! {
+    static ref WORKSPACE_CONFIGURATION_REQUEST: Response = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","result": [
+    {
+        "translationFiles": {
+            "include": [
+                "./fixtures/per_language_file/*.json"
+            ]
+        },
+        "fileName": {
+            "details": "(?P<language>.+?)\\."
+        },
+        "trace": {
+            "server": "verbose"
+        }
+    }
+], "id": 1 }"#
+    )
+    .unwrap();
+    static ref WORKSPACE_SYMBOL_REQUEST: Request = serde_json::from_str(
+        r#"{
+            "jsonrpc":"2.0",
+            "method":"workspace/symbol",
+            "params":{
+                "query": "neder"
+            },
+            "id": 2
+        }"#
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+#[timeout(500)]
+async fn workspace_symbol_matches_value() {
+    let (mut service, _) = prepare_with_workspace_config(&WORKSPACE_CONFIGURATION_REQUEST).await;
+
+    let result = service
+        .call(WORKSPACE_SYMBOL_REQUEST.clone())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let symbols = serde_json::to_value(result).unwrap()["result"].clone();
+    assert_eq!(symbols.as_array().unwrap().len(), 1);
+
+    let symbol = &symbols[0];
+    assert_eq!(symbol["name"], "test");
+    assert_eq!(symbol["kind"], 15);
+    assert_eq!(symbol["containerName"], "nl");
+    assert!(symbol["location"]["uri"]
+        .as_str()
+        .unwrap()
+        .ends_with("per_language_file/nl.json"));
+    assert_eq!(
+        symbol["location"]["range"],
+        serde_json::json!({
+            "start": { "line": 1, "character": 11 },
+            "end": { "line": 1, "character": 21 }
+        })
+    );
+}
@@ -1,7 +1,11 @@
+use std::collections::{HashMap, HashSet};
 use std::ops::Range;
-use std::collections::HashMap;
 
+use crate::plural_helper::split_plural_category;
+use crate::string_helper::TRANSLATION_KEY_DIVIDER;
 use crate::{Definition, ExtensionConfig};
+use itertools::Itertools;
+use tower_lsp::lsp_types::{self, DocumentSymbol, Position, SymbolKind};
 use tree_sitter::{Language, Node, Parser, Query, QueryCursor, QueryMatches};
 
 extern "C" {
@@ -12,7 +16,8 @@ extern "C" {
 
 pub fn get_language_by_extension(ext: &str) -> Option<Language> {
     match ext {
-        "json" => Some(unsafe { tree_sitter_json() }),
+        // ARB files of Flutter are JSON with metadata in `@key` objects
+        "json" | "arb" => Some(unsafe { tree_sitter_json() }),
         "yaml" | "yml" => Some(unsafe { tree_sitter_yaml() }),
         "php" => Some(unsafe { tree_sitter_php() }),
         _ => None,
@@ -21,19 +26,28 @@ pub fn get_language_by_extension(ext: &str) -> Option<Language> {
 
 pub fn get_query_source_by_language(ext: &str) -> Option<&str> {
     match ext {
-        "json" => Some(include_str!("./queries/json.scm")),
+        "json" | "arb" => Some(include_str!("./queries/json.scm")),
         "yaml" | "yml" => Some(include_str!("./queries/yaml.scm")),
         "php" => Some(include_str!("./queries/php.scm")),
         _ => None,
     }
 }
 
+/// A part of a translation file that could not be parsed
+#[derive(Debug, PartialEq)]
+pub struct TranslationError {
+    pub range: lsp_types::Range,
+    pub message: String,
+}
+
+/// Parses the definitions of a translation file. Syntax errors are returned next to
+/// the definitions, so the valid parts of a broken file can still be used.
 pub fn parse_translation_structure(
     text: String,
     config: &ExtensionConfig,
     language: Language,
     query_source: &str,
-) -> Option<Vec<Definition>> {
+) -> (Vec<Definition>, Vec<TranslationError>) {
     let mut parser = Parser::new();
 
     parser.set_language(language).unwrap();
@@ -46,10 +60,12 @@ pub fn parse_translation_structure(
 
     // Execute matches
     let mut definitions = vec![];
+    let mut errors = get_syntax_errors(tree.root_node(), &text);
 
     let matches = query_cursor.matches(&query, tree.root_node(), text.as_bytes());
     for m in matches {
         let mut translation_key = "";
+        let mut translation_key_node: Option<Node> = None;
         let mut translation_value: Option<Node> = None;
         let mut translation_group: Option<Node> = None;
 
@@ -58,13 +74,16 @@ pub fn parse_translation_structure(
 
             if capture_name == "translation_key" {
                 translation_key = &text[capture.node.byte_range()];
+                translation_key_node = Some(capture.node);
             } else if capture_name == "translation_value" {
                 translation_value = Some(capture.node);
             } else if capture_name == "translation_group" {
                 translation_group = Some(capture.node);
             } else if capture_name == "translation_error" {
-                eprintln!("Found an error in the translation file");
-                return None;
+                errors.push(TranslationError {
+                    range: get_range_for_node(capture.node, &text),
+                    message: "Unsupported translation structure".to_string(),
+                });
             }
 
             if !translation_key.is_empty()
@@ -87,13 +106,13 @@ pub fn parse_translation_structure(
                     .trim_matches('"')
                     .to_string();
 
-                definitions.push(Definition {
-                    key: path.clone(),
-                    cleaned_key: get_cleaned_key_for_path(&path, config),
-                    file: None,
-                    extra_data: get_extra_data_for_path(&path, config),
-                    value: translation_value_string,
-                });
+                definitions.push(create_definition(
+                    path,
+                    translation_value_string,
+                    get_range_for_node(translation_value.unwrap(), &text),
+                    get_range_for_node(translation_key_node.unwrap(), &text),
+                    config,
+                ));
 
                 translation_group = None;
                 translation_key = "";
@@ -102,7 +121,416 @@ pub fn parse_translation_structure(
         }
     }
 
-    Some(definitions)
+    resolve_yaml_aliases(tree.root_node(), &text, config, &mut definitions);
+
+    (definitions, errors)
+}
+
+/// Longer unexpected text is cut off in the error message
+const MAX_ERROR_SNIPPET_LENGTH: usize = 20;
+
+/// Collects the ERROR and MISSING nodes of the tree. Errors nested in an ERROR node
+/// are part of the same problem, so only the outermost node is reported.
+fn get_syntax_errors(root: Node, text: &str) -> Vec<TranslationError> {
+    let mut errors = vec![];
+
+    if !root.has_error() {
+        return errors;
+    }
+
+    let mut cursor = root.walk();
+
+    loop {
+        let node = cursor.node();
+
+        if node.is_error() || node.is_missing() {
+            errors.push(TranslationError {
+                range: get_range_for_node(node, text),
+                message: get_syntax_error_message(node, text),
+            });
+        } else if node.has_error() && cursor.goto_first_child() {
+            continue;
+        }
+
+        if cursor.goto_next_sibling() {
+            continue;
+        }
+
+        loop {
+            if !cursor.goto_parent() {
+                return errors;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+}
+
+fn get_syntax_error_message(node: Node, text: &str) -> String {
+    if node.is_missing() {
+        return format!("Missing `{}`", node.kind());
+    }
+
+    // Error nodes can include valid pairs before the actual problem,
+    // so the last line is the most likely place of the mistake
+    let snippet = text[node.byte_range()]
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .last()
+        .unwrap_or("");
+
+    if snippet.is_empty() {
+        return "Syntax error".to_string();
+    }
+
+    if snippet.chars().count() > MAX_ERROR_SNIPPET_LENGTH {
+        format!(
+            "Syntax error near `{}…`",
+            snippet
+                .chars()
+                .take(MAX_ERROR_SNIPPET_LENGTH)
+                .collect::<String>()
+        )
+    } else {
+        format!("Syntax error near `{}`", snippet)
+    }
+}
+
+fn create_definition(
+    path: String,
+    value: String,
+    range: lsp_types::Range,
+    key_range: lsp_types::Range,
+    config: &ExtensionConfig,
+) -> Definition {
+    let mut cleaned_key = get_cleaned_key_for_path(&path, config);
+    let mut plural_category = None;
+
+    // Plural variants are identified by their logical key,
+    // so `item_one` and `item_other` both become `item`
+    if let Some(plural_config) = config.plural.as_ref() {
+        if let Some((logical_key, category)) =
+            split_plural_category(cleaned_key.as_ref().unwrap_or(&path), plural_config)
+        {
+            cleaned_key = Some(logical_key);
+            plural_category = Some(category);
+        }
+    }
+
+    Definition {
+        cleaned_key,
+        file: None,
+        extra_data: get_extra_data_for_path(&path, config),
+        key: path,
+        value,
+        plural_category,
+        range: Some(range),
+        key_range: Some(key_range),
+        merged_from: None,
+    }
+}
+
+/// Returns the LSP range of a node, which counts columns in UTF-16 code units
+/// where tree-sitter counts them in bytes
+pub fn get_range_for_node(node: Node, text: &str) -> lsp_types::Range {
+    let get_position = |byte: usize, row: usize| {
+        let line_start = text[..byte].rfind('\n').map_or(0, |index| index + 1);
+
+        Position::new(
+            row as u32,
+            text[line_start..byte].encode_utf16().count() as u32,
+        )
+    };
+
+    lsp_types::Range::new(
+        get_position(node.start_byte(), node.start_position().row),
+        get_position(node.end_byte(), node.end_position().row),
+    )
+}
+
+/// Nested aliases are resolved in multiple passes, which is limited to prevent
+/// recursive anchors from looping forever
+const MAX_YAML_ALIAS_DEPTH: usize = 10;
+
+/// Adds definitions for YAML aliases (`key: *anchor`) and merge keys (`<<: *anchor`)
+/// by copying the definitions under the anchor to the path of the alias.
+/// Keys that are defined explicitly take precedence over merged keys.
+fn resolve_yaml_aliases(
+    root: Node,
+    text: &String,
+    config: &ExtensionConfig,
+    definitions: &mut Vec<Definition>,
+) {
+    let aliases = get_nodes_by_kind(root, "alias");
+    if aliases.is_empty() {
+        return;
+    }
+
+    let anchors = get_nodes_by_kind(root, "anchor");
+
+    let mut paths = definitions
+        .iter()
+        .map(|definition| definition.key.clone())
+        .collect::<HashSet<String>>();
+
+    for _ in 0..MAX_YAML_ALIAS_DEPTH {
+        let mut new_definitions = vec![];
+
+        for alias in aliases.iter() {
+            let anchor = match get_anchor_for_alias(*alias, &anchors, text) {
+                Some(anchor) => anchor,
+                None => continue,
+            };
+
+            // The anchor is a child of the node it names
+            let anchor_path = get_path_for_node(anchor.parent().unwrap(), text);
+
+            let target_path = match get_merge_key_mapping(*alias, text) {
+                Some(mapping) => get_path_for_node(mapping, text),
+                None => get_path_for_node(*alias, text),
+            };
+
+            for definition in definitions.iter() {
+                let relative_path = match get_relative_yaml_path(&definition.key, &anchor_path) {
+                    Some(relative_path) => relative_path,
+                    None => continue,
+                };
+
+                let path = format!("{}{}", target_path, relative_path);
+                let path = path.strip_prefix('.').unwrap_or(&path).to_string();
+
+                // Merged keys point to the alias, as that is where they appear in the file.
+                // They have no key of their own, so they cannot be removed or annotated.
+                if paths.insert(path.clone()) {
+                    let alias_range = get_range_for_node(*alias, text);
+                    let mut new_definition = create_definition(
+                        path,
+                        definition.value.clone(),
+                        alias_range,
+                        alias_range,
+                        config,
+                    );
+
+                    new_definition.key_range = None;
+                    new_definition.merged_from = Some(
+                        definition
+                            .merged_from
+                            .clone()
+                            .unwrap_or_else(|| definition.key.clone()),
+                    );
+
+                    new_definitions.push(new_definition);
+                }
+            }
+        }
+
+        if new_definitions.is_empty() {
+            break;
+        }
+
+        definitions.append(&mut new_definitions);
+    }
+}
+
+/// Returns the part of `path` below `parent_path` including its leading divider,
+/// or `None` if `path` is not below `parent_path`
+fn get_relative_yaml_path<'a>(path: &'a str, parent_path: &str) -> Option<&'a str> {
+    if parent_path.is_empty() {
+        return Some(path);
+    }
+
+    let relative_path = path.strip_prefix(parent_path)?;
+    if relative_path.is_empty() || relative_path.starts_with('.') || relative_path.starts_with('[')
+    {
+        Some(relative_path)
+    } else {
+        None
+    }
+}
+
+/// Finds the closest anchor before `alias` with the same name within the same document
+fn get_anchor_for_alias<'a>(alias: Node, anchors: &[Node<'a>], text: &String) -> Option<Node<'a>> {
+    let name = text[alias.byte_range()].trim_start_matches('*');
+    let document = get_ancestor_by_kind(alias, "document");
+
+    anchors
+        .iter()
+        .rev()
+        .find(|anchor| {
+            anchor.start_byte() < alias.start_byte()
+                && text[anchor.byte_range()].trim_start_matches('&') == name
+                && get_ancestor_by_kind(**anchor, "document") == document
+        })
+        .copied()
+}
+
+/// Returns the mapping an alias is merged into when it is the value of a `<<` merge key,
+/// either directly (`<<: *a`) or in a sequence (`<<: [*a, *b]`)
+fn get_merge_key_mapping<'a>(alias: Node<'a>, text: &String) -> Option<Node<'a>> {
+    let mut node = alias.parent()?;
+    if node.parent()?.kind() == "flow_sequence" {
+        node = node.parent()?.parent()?;
+    }
+
+    let pair = node.parent()?;
+    if pair.kind() != "block_mapping_pair" && pair.kind() != "flow_pair" {
+        return None;
+    }
+
+    let key = pair.child_by_field_name("key")?;
+    if text[key.byte_range()].trim() == "<<" {
+        pair.parent()
+    } else {
+        None
+    }
+}
+
+fn get_ancestor_by_kind<'a>(node: Node<'a>, kind: &str) -> Option<Node<'a>> {
+    let mut ancestor = node.parent();
+    while let Some(ancestor_node) = ancestor {
+        if ancestor_node.kind() == kind {
+            return Some(ancestor_node);
+        }
+        ancestor = ancestor_node.parent();
+    }
+    None
+}
+
+/// Returns all nodes of `kind` in document order
+fn get_nodes_by_kind<'a>(root: Node<'a>, kind: &str) -> Vec<Node<'a>> {
+    let mut nodes = vec![];
+    let mut cursor = root.walk();
+
+    loop {
+        if cursor.node().kind() == kind {
+            nodes.push(cursor.node());
+        }
+
+        if cursor.goto_first_child() || cursor.goto_next_sibling() {
+            continue;
+        }
+
+        loop {
+            if !cursor.goto_parent() {
+                return nodes;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+}
+
+/// Returns the byte offset of an LSP position, which counts columns in UTF-16 code units
+pub fn get_offset_of_lsp_position(text: &str, position: Position) -> Option<usize> {
+    let line_start = if position.line == 0 {
+        0
+    } else {
+        text.match_indices('\n').nth(position.line as usize - 1)?.0 + 1
+    };
+
+    let mut column = 0;
+    for (index, char) in text[line_start..].char_indices() {
+        if column >= position.character as usize || char == '\n' {
+            return Some(line_start + index);
+        }
+        column += char.len_utf16();
+    }
+
+    Some(text.len())
+}
+
+/// Returns the range to remove to delete the entry whose key starts at `key_start`,
+/// including its line and the comma that separates it from other JSON pairs
+pub fn get_removal_range_for_key(
+    text: &str,
+    language: Language,
+    key_start: Position,
+) -> Option<lsp_types::Range> {
+    let mut parser = Parser::new();
+
+    parser.set_language(language).unwrap();
+
+    let tree = parser.parse(text, None).unwrap();
+
+    let offset = get_offset_of_lsp_position(text, key_start)?;
+    let mut entry = tree.root_node().descendant_for_byte_range(offset, offset)?;
+    while !["pair", "block_mapping_pair", "block_sequence_item"].contains(&entry.kind())
+        && entry
+            .parent()
+            .map_or(true, |parent| parent.kind() != "array")
+    {
+        entry = entry.parent()?;
+    }
+
+    let mut start = entry.start_byte();
+    let mut end = entry.end_byte();
+
+    match (entry.next_sibling(), entry.prev_sibling()) {
+        (Some(next), _) if next.kind() == "," => end = next.end_byte(),
+        (_, Some(previous)) if previous.kind() == "," => start = previous.start_byte(),
+        _ => {}
+    }
+
+    // Removes the whole line when the entry is the only thing on it
+    let line_start = text[..start].rfind('\n').map_or(0, |index| index + 1);
+    let line_end = text[end..]
+        .find('\n')
+        .map_or(text.len(), |index| end + index + 1);
+    if text[line_start..start].trim().is_empty() && text[end..line_end].trim().is_empty() {
+        start = line_start;
+        end = line_end;
+    }
+
+    let get_position = |byte: usize| {
+        let line_start = text[..byte].rfind('\n').map_or(0, |index| index + 1);
+
+        Position::new(
+            text[..byte].matches('\n').count() as u32,
+            text[line_start..byte].encode_utf16().count() as u32,
+        )
+    };
+
+    Some(lsp_types::Range::new(
+        get_position(start),
+        get_position(end),
+    ))
+}
+
+/// Returns the path of the group that contains `offset`, which is where a key typed
+/// at that offset ends up. The key of the pair being typed is not part of the path.
+pub fn get_group_path_at_offset(text: &str, language: Language, offset: usize) -> String {
+    let mut parser = Parser::new();
+
+    parser.set_language(language).unwrap();
+
+    let tree = parser.parse(text, None).unwrap();
+
+    let mut node = match tree.root_node().descendant_for_byte_range(offset, offset) {
+        Some(node) => node,
+        None => return String::new(),
+    };
+
+    let mut ancestor = Some(node);
+    while let Some(ancestor_node) = ancestor {
+        if ancestor_node.kind() == "pair" || ancestor_node.kind() == "block_mapping_pair" {
+            let is_typing_key = ancestor_node
+                .child_by_field_name("key")
+                .map_or(false, |key| {
+                    key.start_byte() <= offset && offset <= key.end_byte()
+                });
+
+            if is_typing_key {
+                node = ancestor_node.parent().unwrap_or(ancestor_node);
+            }
+            break;
+        }
+        ancestor = ancestor_node.parent();
+    }
+
+    get_path_for_node(node, &text.to_string())
 }
 
 /// Recursively goes through a node to fetch the path
@@ -113,25 +541,17 @@ fn get_path_for_node(initial_node: Node, text: &String) -> String {
     loop {
         let node = cursor.node();
         if node.kind() == "pair" || node.kind() == "block_mapping_pair" {
-            let key = node.child_by_field_name("key").unwrap();
-
-            let key_string_node = get_string_content_from_string(key).unwrap();
-
-            let range = match key_string_node.kind() {
-                "single_quote_scalar" | "double_quote_scalar" => {
-                    let original_range = key_string_node.byte_range();
-                    Range {
-                        start: original_range.start + 1,
-                        end: original_range.end - 1,
-                    }
-                }
-                _ => key_string_node.byte_range(),
-            };
-
-            path = format!(".{}{}", &text[range], &path,);
+            path = format!(".{}{}", get_key_of_pair(node, text).unwrap(), &path,);
         } else if node.kind() == "block_sequence_item" {
             let index = get_array_index_of_node(node).unwrap();
 
+            path = format!("[{}]{}", index, &path);
+        } else if node
+            .parent()
+            .map_or(false, |parent| parent.kind() == "array")
+        {
+            let index = get_named_array_index_of_node(node).unwrap();
+
             path = format!("[{}]{}", index, &path);
         }
 
@@ -148,6 +568,103 @@ fn get_path_for_node(initial_node: Node, text: &String) -> String {
     path
 }
 
+/// Returns the unquoted key of a JSON `pair` or YAML `block_mapping_pair`
+fn get_key_of_pair<'a>(pair: Node, text: &'a str) -> Option<&'a str> {
+    let key = pair.child_by_field_name("key")?;
+
+    let key_string_node = get_string_content_from_string(key)?;
+
+    let range = match key_string_node.kind() {
+        "single_quote_scalar" | "double_quote_scalar" => {
+            let original_range = key_string_node.byte_range();
+            Range {
+                start: original_range.start + 1,
+                end: original_range.end - 1,
+            }
+        }
+        _ => key_string_node.byte_range(),
+    };
+
+    Some(&text[range])
+}
+
+/// Returns the outline of a translation file, with a symbol per key that is
+/// nested the same way as the groups `get_path_for_node` walks through
+pub fn get_document_symbols(text: &str, language: Language) -> Vec<DocumentSymbol> {
+    let mut parser = Parser::new();
+
+    parser.set_language(language).unwrap();
+
+    let tree = parser.parse(text, None).unwrap();
+
+    get_document_symbols_for_node(tree.root_node(), text)
+}
+
+#[allow(deprecated)]
+fn get_document_symbols_for_node(node: Node, text: &str) -> Vec<DocumentSymbol> {
+    let mut symbols = vec![];
+    let mut cursor = node.walk();
+
+    let children = node
+        .named_children(&mut cursor)
+        .filter(|child_node| child_node.kind() != "comment")
+        .collect::<Vec<Node>>();
+
+    for (index, child_node) in children.iter().enumerate() {
+        let (name, selection_node, value_node) = match child_node.kind() {
+            "pair" | "block_mapping_pair" => match get_key_of_pair(*child_node, text) {
+                Some(key) => (
+                    key.to_string(),
+                    child_node.child_by_field_name("key").unwrap(),
+                    child_node.child_by_field_name("value"),
+                ),
+                None => continue,
+            },
+            "block_sequence_item" => (
+                format!("[{}]", get_array_index_of_node(*child_node).unwrap()),
+                *child_node,
+                child_node.named_child(0),
+            ),
+            _ if node.kind() == "array" => (format!("[{}]", index), *child_node, Some(*child_node)),
+            _ => {
+                symbols.append(&mut get_document_symbols_for_node(*child_node, text));
+                continue;
+            }
+        };
+
+        let value_symbols = value_node
+            .map(|value_node| get_document_symbols_for_node(value_node, text))
+            .unwrap_or_default();
+
+        let (kind, detail, children) = if value_symbols.is_empty() {
+            let value = value_node.map(|value_node| {
+                text[value_node.byte_range()]
+                    .trim_matches('\'')
+                    .trim_matches('"')
+                    .split_whitespace()
+                    .join(" ")
+            });
+
+            (SymbolKind::STRING, value, None)
+        } else {
+            (SymbolKind::OBJECT, None, Some(value_symbols))
+        };
+
+        symbols.push(DocumentSymbol {
+            name,
+            detail,
+            kind,
+            tags: None,
+            deprecated: None,
+            range: get_range_for_node(*child_node, text),
+            selection_range: get_range_for_node(selection_node, text),
+            children,
+        });
+    }
+
+    symbols
+}
+
 static STRING_CONTENT_KINDS: &[&str] = &[
     // JSON
     "string_content",
@@ -169,6 +686,23 @@ fn get_array_index_of_node(node: Node) -> Option<usize> {
     None
 }
 
+/// Like `get_array_index_of_node`, but skips the brackets, commas and comments of a JSON array
+fn get_named_array_index_of_node(node: Node) -> Option<usize> {
+    let parent_node = node.parent()?;
+
+    let mut cursor = parent_node.walk();
+    let named_children = parent_node
+        .named_children(&mut cursor)
+        .filter(|child_node| child_node.kind() != "comment");
+
+    for (index, child_node) in named_children.enumerate() {
+        if child_node == node {
+            return Some(index);
+        }
+    }
+    None
+}
+
 fn get_string_content_from_string(string: Node) -> Option<Node> {
     let mut value_cursor = string.walk();
     value_cursor.goto_first_child();
@@ -194,6 +728,30 @@ fn get_string_content_from_string(string: Node) -> Option<Node> {
     None
 }
 
+/// Applies the key filter to the path of a group, so it can be compared with the identifiers
+/// of definitions. The filter is written for keys, so it is applied to a key inside the group.
+pub fn get_cleaned_group_path(group_path: &str, config: &ExtensionConfig) -> String {
+    // A segment that is never part of a real key
+    let placeholder = "\u{0}";
+
+    let key = if group_path.is_empty() {
+        placeholder.to_string()
+    } else {
+        format!("{}{}{}", group_path, TRANSLATION_KEY_DIVIDER, placeholder)
+    };
+
+    match get_cleaned_key_for_path(&key, config) {
+        Some(cleaned_key) => match cleaned_key.strip_suffix(placeholder) {
+            Some(cleaned_group_path) => cleaned_group_path
+                .strip_suffix(TRANSLATION_KEY_DIVIDER)
+                .unwrap_or(cleaned_group_path)
+                .to_string(),
+            None => group_path.to_string(),
+        },
+        None => group_path.to_string(),
+    }
+}
+
 fn get_cleaned_key_for_path(path: &String, config: &ExtensionConfig) -> Option<String> {
     config.key.filter.as_ref().and_then(|key_filter_regex| {
         key_filter_regex
@@ -202,10 +760,7 @@ fn get_cleaned_key_for_path(path: &String, config: &ExtensionConfig) -> Option<S
     })
 }
 
-fn get_extra_data_for_path(
-    path: &String,
-    config: &ExtensionConfig,
-) -> HashMap<String, String> {
+fn get_extra_data_for_path(path: &String, config: &ExtensionConfig) -> HashMap<String, String> {
     let mut extra_data = HashMap::<String, String>::new();
 
     if let Some(key_details_regex) = config.key.details.as_ref() {